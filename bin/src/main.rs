@@ -1,9 +1,14 @@
-use std::{fmt, io::{self, Read}};
+use std::{
+    env, fmt, fs, io::{self, BufRead, IsTerminal, Read, Write}, path::{Path, PathBuf}, process,
+};
 
-use clap::Parser;
-use libshire::strings::CappedString;
-use tabled::{Tabled, Table, Style};
-use utfdump::{CombiningClass, Category, utf8::{Utf8Decode, Utf8Error}, StaticUnicodeData};
+use clap::{Parser, ValueEnum};
+use tabled::{Tabled, Table, Style, Disable};
+use unicode_width::UnicodeWidthStr;
+use utfdump::{
+    BidiCategory, CombiningClass, Category, CharData, char_data_why, utf8::{Utf8Decode, Utf8Error},
+    StaticUnicodeData, UcdDecompMapping, UnicodeData,
+};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -11,118 +16,1476 @@ struct Args {
     /// Display category names in plain English, rather than using their abbreviated names
     #[clap(short, long, action)]
     full_category_names: bool,
+
+    /// Display an extra column with each codepoint written as an escape sequence in the given style
+    #[clap(short, long, value_enum)]
+    escape: Option<EscapeStyle>,
+
+    /// Read a list of codepoints from the given file instead of reading UTF-8 from stdin. Each line
+    /// should contain one codepoint, written as U+XXXX, 0xXX, a decimal number or an escape sequence
+    /// such as \u{1F600} or \xC3\xA9
+    #[clap(long)]
+    codepoints_file: Option<PathBuf>,
+
+    /// Truncate character names longer than the given number of characters, marking the
+    /// truncation with an ellipsis. When writing to a terminal whose width is given by the COLUMNS
+    /// environment variable, names are truncated to fit the table to that width by default; 0
+    /// disables truncation entirely
+    #[clap(long)]
+    max_name_width: Option<usize>,
+
+    /// Sort the output rows by the given key, rather than displaying them in input order. Invalid
+    /// sequences are placed after all valid characters
+    #[clap(short, long, value_enum)]
+    sort: Option<SortKey>,
+
+    /// Print every known property of a single character as a detailed listing, instead of reading
+    /// from stdin. The character can be given directly or as a codepoint written as U+XXXX, 0xXX, a
+    /// decimal number or an escape sequence such as \u{1F600} or \xC3\xA9
+    #[clap(long, value_name = "CHAR")]
+    explain: Option<String>,
+
+    /// Print the number of bytes, characters and invalid sequences read from stdin, instead of
+    /// displaying a table
+    #[clap(
+        long,
+        action,
+        conflicts_with_all = &["codepoints-file", "explain", "diff", "by-line", "sort"],
+    )]
+    count: bool,
+
+    /// Compare two uncompressed Unicode data files, printing the codepoints which were added,
+    /// removed, renamed or recategorized between them
+    #[clap(long, number_of_values = 2, value_names = &["OLD", "NEW"])]
+    diff: Option<Vec<PathBuf>>,
+
+    /// Print a summary of each line read from stdin, instead of a row for each character. The
+    /// summary lists any invisible formatting characters in the line, such as bidirectional
+    /// controls
+    #[clap(long, action, conflicts_with = "codepoints-file")]
+    by_line: bool,
+
+    /// With --by-line, only print lines containing invalid sequences or invisible formatting
+    /// characters
+    #[clap(long, action, requires = "by-line")]
+    only_suspicious: bool,
+
+    /// Display an extra column with the given data for each character. Can be given more than
+    /// once, or as a comma-separated list
+    #[clap(long = "column", value_enum, value_name = "COLUMN", value_delimiter = ',')]
+    columns: Vec<ExtraColumn>,
+
+    /// Display every extra column which can be chosen with --column
+    #[clap(long, action)]
+    all_columns: bool,
+
+    /// Do not print the header row of the table
+    #[clap(long, action)]
+    no_header: bool,
+
+    /// Write the output to the given file, rather than to stdout
+    #[clap(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// The format to write each decoded character in. With ndjson, one JSON object is written per
+    /// line as soon as each character is decoded, rather than buffering the whole input into a
+    /// table
+    #[clap(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        conflicts_with_all = &["sort", "explain", "count", "diff", "by-line"],
+    )]
+    format: Option<OutputFormat>,
+
+    /// The encoding of the input text. With auto, UTF-16 or UTF-32 is detected from a byte order
+    /// mark at the start of the input, falling back to UTF-8. A byte order mark is still displayed
+    /// as a character rather than being skipped. For encodings other than UTF-8, an extra column
+    /// shows the input bytes each character was decoded from
+    #[clap(
+        long,
+        value_enum,
+        value_name = "ENCODING",
+        conflicts_with_all = &["codepoints-file", "by-line"],
+    )]
+    input_encoding: Option<InputEncoding>,
+
+    /// Read the input as a hex dump of the bytes to decode, such as "ce ba e1 bd b9". Bytes may be
+    /// separated by whitespace and prefixed with 0x
+    #[clap(long, action, conflicts_with_all = &["codepoints-file", "by-line"])]
+    hex: bool,
+
+    /// Text to inspect, instead of reading from stdin. Multiple arguments are joined with spaces
+    #[clap(value_name = "TEXT", conflicts_with_all = &["codepoints-file", "explain", "diff"])]
+    text: Vec<String>,
+}
+
+impl Args {
+    /// The maximum width of a character name given by `--max-name-width`, where 0 means no limit.
+    fn name_width_limit(&self) -> Option<usize> {
+        self.max_name_width.filter(|&width| width != 0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum EscapeStyle {
+    /// Rust-style escape, e.g. \u{1f600}
+    Rust,
+    /// Python-style escape, e.g. \U0001f600
+    Python,
+    /// HTML hexadecimal numeric character reference, e.g. &#x1f600;
+    Html,
+    /// HTML decimal numeric character reference, e.g. &#128512;
+    HtmlDec,
+    /// Plain decimal codepoint, e.g. 128512
+    Decimal,
 }
 
-fn main() {
-    let unicode_data = StaticUnicodeData::new().unwrap();
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ExtraColumn {
+    /// The UTF-16 code units
+    Utf16,
+    /// The UTF-16 encoding as little-endian bytes
+    Utf16le,
+    /// The UTF-16 encoding as big-endian bytes
+    Utf16be,
+    /// The UTF-32 code unit
+    Utf32,
+    /// The bidirectional category
+    Bidi,
+    /// The decomposition mapping, in the form used by UnicodeData.txt
+    Decomposition,
+    /// The numeric value, which may be a fraction
+    Numeric,
+    /// The decimal digit value, for characters used as digits in decimal numbers
+    Decimal,
+    /// The digit value, which also covers digits that are not used in decimal numbers, such as
+    /// superscripts
+    Digit,
+    /// Whether the character is mirrored in right-to-left text
+    Mirrored,
+    /// The character's name in Unicode 1.0, if it differs from its current name
+    #[clap(name = "unicode-1-name")]
+    Unicode1Name,
+    /// The ISO 10646 comment field
+    Comment,
+}
+
+impl ExtraColumn {
+    const ALL: [Self; 12] = [
+        Self::Utf16,
+        Self::Utf16le,
+        Self::Utf16be,
+        Self::Utf32,
+        Self::Bidi,
+        Self::Decomposition,
+        Self::Numeric,
+        Self::Decimal,
+        Self::Digit,
+        Self::Mirrored,
+        Self::Unicode1Name,
+        Self::Comment,
+    ];
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Utf16 => "UTF-16",
+            Self::Utf16le => "UTF-16LE",
+            Self::Utf16be => "UTF-16BE",
+            Self::Utf32 => "UTF-32",
+            Self::Bidi => "Bidi",
+            Self::Decomposition => "Decomposition",
+            Self::Numeric => "Numeric",
+            Self::Decimal => "Decimal",
+            Self::Digit => "Digit",
+            Self::Mirrored => "Mirrored",
+            Self::Unicode1Name => "Unicode 1.0 Name",
+            Self::Comment => "Comment",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum InputEncoding {
+    Utf8,
+    /// Little-endian UTF-16
+    Utf16le,
+    /// Big-endian UTF-16
+    Utf16be,
+    /// Little-endian UTF-32
+    Utf32le,
+    /// Big-endian UTF-32
+    Utf32be,
+    /// ISO-8859-1, where each byte is the codepoint with the same value
+    Latin1,
+    /// Windows-1252, which replaces the C1 control codes of Latin-1 with printable characters
+    Cp1252,
+    /// UTF-16 or UTF-32 if the input starts with a byte order mark, otherwise UTF-8
+    Auto,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// A table with one row per character
+    Table,
+    /// Newline-delimited JSON, with one object per character
+    Ndjson,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SortKey {
+    Codepoint,
+    Name,
+    Category,
+}
+
+fn main() {
+    let unicode_data = StaticUnicodeData::new().unwrap();
+
+    let args = Args::parse();
+
+    if let Some(explain) = &args.explain {
+        let c = match parse_char_or_codepoint(explain) {
+            Ok(c) => c,
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            },
+        };
+
+        print_table(Table::new(explain_rows(c)), &args);
+        return;
+    }
+
+    if let Some(paths) = &args.diff {
+        let (old_path, new_path) = (&paths[0], &paths[1]);
+
+        let old_bytes = exit_on_err(fs::read(old_path), old_path);
+        let new_bytes = exit_on_err(fs::read(new_path), new_path);
+        let old_data = exit_on_err(UnicodeData::from_bytes(&old_bytes), old_path);
+        let new_data = exit_on_err(UnicodeData::from_bytes(&new_bytes), new_path);
+
+        print_table(Table::new(diff_rows(old_data, new_data, &args)), &args);
+        return;
+    }
+
+    if args.by_line {
+        let rows = line_rows(&unicode_data, input_reader(&args), args.only_suspicious);
+        print_table(Table::new(rows), &args);
+        return;
+    }
+
+    let input_encoding = args.input_encoding.unwrap_or(InputEncoding::Utf8);
+
+    if args.count {
+        let bytes = read_input(&args);
+        let (_, chars) = decode_input(bytes.iter().copied(), input_encoding);
+        let (num_chars, num_invalid) = chars
+            .fold((0, 0), |(chars, errors), res| match res {
+                Ok(_) => (chars + 1, errors),
+                Err(_) => (chars, errors + 1),
+            });
+
+        write_output(&args, format_args!(
+            "bytes: {}\nchars: {}\ninvalid: {}",
+            bytes.len(), num_chars, num_invalid
+        ));
+        return;
+    }
+
+    if args.format == Some(OutputFormat::Ndjson) {
+        let (encoding, chars): (_, DecodedChars) = match &args.codepoints_file {
+            Some(path) => match read_codepoints_file(path) {
+                Ok(chars) => (InputEncoding::Utf8, Box::new(chars.into_iter().map(Ok))),
+                Err(err) => {
+                    eprintln!("{}: {}", path.display(), err);
+                    process::exit(1);
+                },
+            },
+            // The hex dump has to be read in full before it can be checked for errors.
+            None if args.hex => decode_input(read_input(&args).into_iter(), input_encoding),
+            None => decode_input(
+                input_reader(&args).bytes().map(|b| b.expect("failed to read input")),
+                input_encoding,
+            ),
+        };
+
+        with_output(&args, |out| {
+            for c in chars {
+                write_ndjson_row(out, &unicode_data, c, encoding, &args)?;
+            }
+            Ok(())
+        });
+        return;
+    }
+
+    let (encoding, mut chars) = match &args.codepoints_file {
+        Some(path) => match read_codepoints_file(path) {
+            Ok(chars) => (InputEncoding::Utf8, chars.into_iter().map(Ok).collect::<Vec<_>>()),
+            Err(err) => {
+                eprintln!("{}: {}", path.display(), err);
+                process::exit(1);
+            },
+        },
+        None => {
+            let (encoding, chars) = decode_input(read_input(&args).into_iter(), input_encoding);
+            (encoding, chars.collect::<Vec<_>>())
+        },
+    };
+
+    if let Some(sort_key) = args.sort {
+        sort_chars(&unicode_data, &mut chars, sort_key);
+    }
+
+    let mut rows = chars
+        .into_iter()
+        .map(|c| OutRow::from_char_result(&unicode_data, c, encoding, &args))
+        .collect::<Vec<_>>();
+
+    let mut hidden_columns = ExtraColumn::ALL
+        .iter()
+        .filter(|&&column| !args.all_columns && !args.columns.contains(&column))
+        .map(|column| column.header())
+        .collect::<Vec<_>>();
+
+    if args.escape.is_none() {
+        hidden_columns.push("Escape");
+    }
+
+    // For UTF-8 input, the input bytes are the same as the UTF-8 column.
+    if encoding == InputEncoding::Utf8 {
+        hidden_columns.push("Input");
+    }
+
+    if args.max_name_width.is_none() && args.output.is_none() {
+        if let Some(terminal_width) = terminal_width() {
+            fit_names_to_width(&mut rows, &hidden_columns, terminal_width, &args);
+        }
+    }
+
+    print_table(disable_columns(Table::new(&rows), &hidden_columns), &args);
+}
+
+fn print_table(table: Table, args: &Args) {
+    write_output(args, style_table(table, args));
+}
+
+fn style_table(table: Table, args: &Args) -> Table {
+    let mut table = table
+        .with(Style::modern());
+
+    if args.no_header {
+        table = table.with(Disable::Row(..1));
+    }
+
+    table
+}
+
+/// When fitting the table to the terminal, names are never truncated to fewer characters than
+/// this, even if the table is still too wide.
+const MIN_FITTED_NAME_WIDTH: usize = 16;
+
+/// Truncates the names in `rows` so that the table fits in the given width, if it does not already.
+fn fit_names_to_width(
+    rows: &mut [OutRow],
+    hidden_columns: &[&str],
+    width: usize,
+    args: &Args
+) {
+    let table_width = table_width(rows, hidden_columns, args);
+
+    if table_width <= width {
+        return;
+    }
+
+    let longest_name = rows.iter()
+        .filter_map(|row| match &row.name {
+            Optional::Some(name) => Some(name.name.chars().count()),
+            Optional::None => None,
+        })
+        .max()
+        .unwrap_or(0);
+
+    let max_width = longest_name
+        .saturating_sub(table_width - width)
+        .max(MIN_FITTED_NAME_WIDTH);
+
+    for row in rows {
+        if let Optional::Some(name) = &mut row.name {
+            name.max_width = Some(max_width);
+        }
+    }
+}
+
+/// The width of the table that `print_table` would print for the given rows, computed from the
+/// widths of the cells rather than by rendering the table.
+fn table_width(rows: &[OutRow], hidden_columns: &[&str], args: &Args) -> usize {
+    let headers = OutRow::headers();
+
+    let mut widths = headers
+        .iter()
+        .map(|header| if args.no_header { 0 } else { header.width() })
+        .collect::<Vec<_>>();
+
+    for row in rows {
+        for (width, field) in widths.iter_mut().zip(row.fields()) {
+            *width = (*width).max(field.width());
+        }
+    }
+
+    let widths = headers
+        .iter()
+        .zip(widths)
+        .filter(|(header, _)| !hidden_columns.contains(&header.as_str()))
+        .map(|(_, width)| width)
+        .collect::<Vec<_>>();
+
+    // Each cell is padded with a space on either side, and there is a border between each pair of
+    // columns as well as on either side of the table.
+    widths.iter().sum::<usize>() + widths.len() * 3 + 1
+}
+
+/// The width of the terminal, if stdout is a terminal and its width is given by the `COLUMNS`
+/// environment variable.
+fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|&columns| columns > 0)
+}
+
+/// Writes the given output followed by a newline to the file given by `--output`, or to stdout if
+/// there is no output file, exiting the process if the output cannot be written.
+fn write_output<T>(args: &Args, output: T)
+where
+    T: fmt::Display,
+{
+    with_output(args, |out| writeln!(out, "{}", output));
+}
+
+/// Runs `f` with a writer for the output file given by `--output`, or stdout if there is none,
+/// exiting the process if writing fails. Both are flushed after every line, so streamed output
+/// shows up as it is written when followed with `tail -f` or read through a pager.
+fn with_output<F>(args: &Args, f: F)
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    let res = match &args.output {
+        Some(path) => fs::File::create(path)
+            .map(io::LineWriter::new)
+            .and_then(|mut file| {
+                f(&mut file)?;
+                file.flush()
+            }),
+        None => {
+            let mut stdout = io::stdout().lock();
+            f(&mut stdout).and_then(|()| stdout.flush())
+        },
+    };
+
+    if let Err(err) = res {
+        match &args.output {
+            Some(path) => eprintln!("{}: {}", path.display(), err),
+            None => eprintln!("{}", err),
+        }
+        process::exit(1);
+    }
+}
+
+fn exit_on_err<T, E>(res: Result<T, E>, path: &Path) -> T
+where
+    E: fmt::Display,
+{
+    res.unwrap_or_else(|err| {
+        eprintln!("{}: {}", path.display(), err);
+        process::exit(1);
+    })
+}
+
+fn sort_chars(
+    unicode_data: &StaticUnicodeData,
+    chars: &mut [Result<char, InputError>],
+    sort_key: SortKey
+) {
+    // Sorting is stable, so invalid sequences (which have no key) keep their relative order, as do
+    // characters with equal keys.
+    match sort_key {
+        SortKey::Codepoint => {
+            chars.sort_by_key(|c| c.as_ref().ok().copied().ok_or(()));
+        },
+        SortKey::Name => {
+            chars.sort_by_cached_key(|c| c.as_ref().ok().map(|&c| {
+                unicode_data.name(u32::from(c))
+            }).ok_or(()));
+        },
+        SortKey::Category => {
+            chars.sort_by_cached_key(|c| c.as_ref().ok().map(|&c| {
+                unicode_data.get(u32::from(c)).map(|char_data| char_data.category())
+            }).ok_or(()));
+        },
+    }
+}
+
+/// Returns a reader for the text given on the command line, or for stdin if there is none.
+fn input_reader(args: &Args) -> Box<dyn BufRead> {
+    if args.text.is_empty() {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(io::Cursor::new(args.text.join(" ").into_bytes()))
+    }
+}
+
+/// Reads the whole input, decoding it from hex first if `--hex` was given.
+fn read_input(args: &Args) -> Vec<u8> {
+    let mut buf = Vec::<u8>::new();
+    input_reader(args)
+        .read_to_end(&mut buf)
+        .expect("failed to read input");
+
+    if !args.hex {
+        return buf;
+    }
+
+    match parse_hex_bytes(&String::from_utf8_lossy(&buf)) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        },
+    }
+}
+
+/// Parses a whitespace-separated hex dump such as "ce ba 0xe1 bdb9" into the bytes it describes.
+/// Each word may contain any number of whole bytes.
+fn parse_hex_bytes(s: &str) -> Result<Vec<u8>, InvalidHexError> {
+    let mut bytes = Vec::new();
+
+    for word in s.split_whitespace() {
+        let digits = word
+            .strip_prefix("0x")
+            .or_else(|| word.strip_prefix("0X"))
+            .unwrap_or(word);
+
+        if digits.is_empty()
+            || digits.len() % 2 != 0
+            || !digits.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            return Err(InvalidHexError(word.to_owned()));
+        }
+
+        // All of the digits are ASCII, so every pair is on a character boundary.
+        for i in (0..digits.len()).step_by(2) {
+            bytes.push(u8::from_str_radix(&digits[i..(i + 2)], 16).unwrap());
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[derive(Debug)]
+struct InvalidHexError(String);
+
+impl fmt::Display for InvalidHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid hex bytes \"{}\"", self.0)
+    }
+}
+
+type DecodedChars<'a> = Box<dyn Iterator<Item = Result<char, InputError>> + 'a>;
+
+/// Decodes the input bytes using the given encoding, returning the encoding that was actually used
+/// (which is only different for `InputEncoding::Auto`) along with the decoded characters.
+fn decode_input<'a, I>(mut bytes: I, encoding: InputEncoding) -> (InputEncoding, DecodedChars<'a>)
+where
+    I: Iterator<Item = u8> + 'a,
+{
+    // Auto-detection needs to look at the first four bytes, so take them from the input here and
+    // put them back in front of the rest of it afterwards.
+    let prefix = match encoding {
+        InputEncoding::Auto => bytes.by_ref().take(4).collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+
+    // The UTF-32LE byte order mark starts with the UTF-16LE one, so it has to be checked first.
+    let encoding = match (encoding, &prefix[..]) {
+        (InputEncoding::Auto, [0xff, 0xfe, 0x00, 0x00]) => InputEncoding::Utf32le,
+        (InputEncoding::Auto, [0x00, 0x00, 0xfe, 0xff]) => InputEncoding::Utf32be,
+        (InputEncoding::Auto, [0xff, 0xfe, ..]) => InputEncoding::Utf16le,
+        (InputEncoding::Auto, [0xfe, 0xff, ..]) => InputEncoding::Utf16be,
+        (InputEncoding::Auto, _) => InputEncoding::Utf8,
+        (encoding, _) => encoding,
+    };
+
+    let bytes = prefix.into_iter().chain(bytes);
+
+    let chars: DecodedChars = match encoding {
+        InputEncoding::Utf8 | InputEncoding::Auto => {
+            Box::new(bytes.decode_utf8().map(|c| c.map_err(InputError::Utf8)))
+        },
+        InputEncoding::Utf16le => Box::new(Utf16Decoder::new(bytes, false)),
+        InputEncoding::Utf16be => Box::new(Utf16Decoder::new(bytes, true)),
+        InputEncoding::Utf32le => Box::new(Utf32Decoder::new(bytes, false)),
+        InputEncoding::Utf32be => Box::new(Utf32Decoder::new(bytes, true)),
+        InputEncoding::Latin1 => Box::new(bytes.map(|b| Ok(char::from(b)))),
+        InputEncoding::Cp1252 => Box::new(bytes.map(|b| Ok(cp1252_to_char(b)))),
+    };
+
+    (encoding, chars)
+}
+
+/// An invalid sequence in the input, in whichever encoding it was read as.
+#[derive(Debug)]
+enum InputError {
+    Utf8(Utf8Error),
+    /// A UTF-16 surrogate code unit which is not part of a surrogate pair.
+    UnpairedSurrogate { unit: u16, bytes: [u8; 2] },
+    /// A UTF-32 code unit which is a surrogate or is above U+10FFFF.
+    InvalidCodeUnit { unit: u32, bytes: [u8; 4] },
+    /// Bytes left over at the end of UTF-16 or UTF-32 input which are too few to make up a whole
+    /// code unit.
+    IncompleteCodeUnit { bytes: [u8; 4], len: usize },
+}
+
+impl InputError {
+    /// The bytes of the input which make up the invalid sequence.
+    fn into_bad_bytes(self) -> InputBytes {
+        match self {
+            Self::Utf8(err) => {
+                let (buf, _num_bad_bytes, len) = err.into_parts();
+                InputBytes { buf, len }
+            },
+            Self::UnpairedSurrogate { bytes: [b0, b1], .. } => {
+                InputBytes { buf: [b0, b1, 0, 0], len: 2 }
+            },
+            Self::InvalidCodeUnit { bytes, .. } => InputBytes { buf: bytes, len: 4 },
+            Self::IncompleteCodeUnit { bytes, len } => InputBytes { buf: bytes, len },
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Utf8(err) => write!(f, "{}", err.kind()),
+            Self::UnpairedSurrogate { unit, .. } => write!(f, "unpaired surrogate 0x{:04x}", unit),
+            Self::InvalidCodeUnit { unit: unit @ 0xd800..=0xdfff, .. } => {
+                write!(f, "surrogate code unit 0x{:08x}", unit)
+            },
+            Self::InvalidCodeUnit { unit, .. } => {
+                write!(f, "code unit 0x{:08x} out of range", unit)
+            },
+            Self::IncompleteCodeUnit { .. } => f.write_str("incomplete code unit"),
+        }
+    }
+}
+
+/// Decodes UTF-16 code units, read as pairs of bytes in the given byte order.
+struct Utf16Decoder<I> {
+    bytes: I,
+    big_endian: bool,
+    /// A code unit which was read while looking for the low half of a surrogate pair, but which
+    /// turned out not to be one.
+    peeked: Option<Result<[u8; 2], u8>>,
+}
+
+impl<I> Utf16Decoder<I>
+where
+    I: Iterator<Item = u8>,
+{
+    fn new(bytes: I, big_endian: bool) -> Self {
+        Self { bytes, big_endian, peeked: None }
+    }
+
+    /// Returns the bytes of the next code unit, or the last byte of the input if there is only one
+    /// byte left.
+    fn next_unit(&mut self) -> Option<Result<[u8; 2], u8>> {
+        if let Some(peeked) = self.peeked.take() {
+            return Some(peeked);
+        }
+
+        let first = self.bytes.next()?;
+        match self.bytes.next() {
+            Some(second) => Some(Ok([first, second])),
+            None => Some(Err(first)),
+        }
+    }
+
+    fn unit_value(&self, bytes: [u8; 2]) -> u16 {
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+}
+
+impl<I> Iterator for Utf16Decoder<I>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = Result<char, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = match self.next_unit()? {
+            Ok(bytes) => bytes,
+            Err(b) => {
+                return Some(Err(InputError::IncompleteCodeUnit { bytes: [b, 0, 0, 0], len: 1 }));
+            },
+        };
+
+        let unit = self.unit_value(bytes);
+
+        match unit {
+            0xd800..=0xdbff => match self.next_unit() {
+                Some(Ok(low_bytes)) if (0xdc00..=0xdfff).contains(&self.unit_value(low_bytes)) => {
+                    let high = u32::from(unit) - 0xd800;
+                    let low = u32::from(self.unit_value(low_bytes)) - 0xdc00;
+                    Some(Ok(char::from_u32(0x10000 + (high << 10) + low).unwrap()))
+                },
+                next => {
+                    // Put back whatever followed the high surrogate, so that it is decoded on its
+                    // own.
+                    self.peeked = next;
+                    Some(Err(InputError::UnpairedSurrogate { unit, bytes }))
+                },
+            },
+            0xdc00..=0xdfff => Some(Err(InputError::UnpairedSurrogate { unit, bytes })),
+            _ => Some(Ok(char::from_u32(u32::from(unit)).unwrap())),
+        }
+    }
+}
+
+/// Windows-1252 differs from Latin-1 only in the range 0x80 to 0x9f. The five bytes in this range
+/// which Windows-1252 leaves undefined are mapped to the C1 control codes with the same value, as
+/// the WHATWG Encoding Standard does.
+const CP1252_80_TO_9F: [char; 32] = [
+    '\u{20ac}', '\u{0081}', '\u{201a}', '\u{0192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02c6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008d}', '\u{017d}', '\u{008f}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02dc}', '\u{2122}', '\u{0161}', '\u{203a}', '\u{0153}', '\u{009d}', '\u{017e}', '\u{0178}',
+];
+
+fn cp1252_to_char(b: u8) -> char {
+    match b {
+        0x80..=0x9f => CP1252_80_TO_9F[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
+
+fn char_to_cp1252(c: char) -> Option<u8> {
+    match u32::from(c) {
+        0x00..=0x7f | 0xa0..=0xff => u8::try_from(c).ok(),
+        _ => CP1252_80_TO_9F
+            .iter()
+            .position(|&mapped| mapped == c)
+            .and_then(|i| u8::try_from(i + 0x80).ok()),
+    }
+}
+
+/// Decodes UTF-32 code units, read as groups of four bytes in the given byte order.
+struct Utf32Decoder<I> {
+    bytes: I,
+    big_endian: bool,
+}
+
+impl<I> Utf32Decoder<I> {
+    fn new(bytes: I, big_endian: bool) -> Self {
+        Self { bytes, big_endian }
+    }
+}
+
+impl<I> Iterator for Utf32Decoder<I>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = Result<char, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0u8; 4];
+
+        for (len, slot) in bytes.iter_mut().enumerate() {
+            match self.bytes.next() {
+                Some(b) => *slot = b,
+                None if len == 0 => return None,
+                None => return Some(Err(InputError::IncompleteCodeUnit { bytes, len })),
+            }
+        }
+
+        let unit = if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+
+        Some(char::from_u32(unit).ok_or(InputError::InvalidCodeUnit { unit, bytes }))
+    }
+}
+
+fn line_rows(
+    unicode_data: &StaticUnicodeData,
+    reader: Box<dyn BufRead>,
+    only_suspicious: bool
+) -> Vec<LineRow>
+{
+    reader.split(b'\n')
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.expect("failed to read input");
+            LineRow::new(unicode_data, i + 1, &line)
+        })
+        .filter(|row| !only_suspicious || row.is_suspicious())
+        .collect()
+}
+
+fn read_codepoints_file(path: &Path) -> Result<Vec<char>, CodepointsFileError> {
+    let contents = fs::read_to_string(path)
+        .map_err(CodepointsFileError::Io)?;
+
+    let mut chars = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let c = parse_codepoint(line)
+            .map_err(|err| CodepointsFileError::Parse { line: i + 1, err })?;
+
+        chars.push(c);
+    }
+
+    Ok(chars)
+}
+
+fn parse_codepoint(s: &str) -> Result<char, ParseCodepointError> {
+    let s = s.trim();
+
+    if s.starts_with('\\') {
+        return parse_escaped_codepoint(s);
+    }
+
+    let (digits, radix) = if let Some(hex) = s.strip_prefix("U+").or_else(|| s.strip_prefix("u+")) {
+        (hex, 16)
+    } else if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        (hex, 16)
+    } else {
+        (s, 10)
+    };
+
+    let codepoint = u32::from_str_radix(digits, radix)
+        .map_err(|_| ParseCodepointError::Malformed(s.to_owned()))?;
+
+    char::try_from(codepoint)
+        .map_err(|_| ParseCodepointError::NotScalarValue(codepoint))
+}
+
+/// Parses a codepoint written as a source code escape sequence: `\u{1F600}`, `\u1F600`,
+/// `\U0001F600`, or a sequence of byte escapes such as `\xC3\xA9` which encode a single character
+/// as UTF-8.
+fn parse_escaped_codepoint(s: &str) -> Result<char, ParseCodepointError> {
+    if s.starts_with("\\x") {
+        return parse_byte_escapes(s);
+    }
+
+    let hex = s.strip_prefix("\\u{")
+        .and_then(|hex| hex.strip_suffix('}'))
+        .or_else(|| s.strip_prefix("\\u"))
+        .or_else(|| s.strip_prefix("\\U"))
+        .ok_or_else(|| ParseCodepointError::UnknownEscape(s.to_owned()))?;
+
+    let codepoint = u32::from_str_radix(hex, 16)
+        .map_err(|_| ParseCodepointError::Malformed(s.to_owned()))?;
+
+    char::try_from(codepoint)
+        .map_err(|_| ParseCodepointError::NotScalarValue(codepoint))
+}
+
+fn parse_byte_escapes(s: &str) -> Result<char, ParseCodepointError> {
+    let bytes = s.split("\\x")
+        .skip(1)
+        .map(|hex| match hex.len() {
+            2 => u8::from_str_radix(hex, 16).ok(),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| ParseCodepointError::Malformed(s.to_owned()))?;
+
+    let mut chars = bytes.decode_utf8();
+
+    match (chars.next(), chars.next()) {
+        (Some(Ok(c)), None) => Ok(c),
+        _ => Err(ParseCodepointError::NotSingleChar(s.to_owned())),
+    }
+}
+
+/// Parses a string consisting of either a single character or a codepoint. A single digit is
+/// treated as the digit character itself rather than as a decimal codepoint.
+fn parse_char_or_codepoint(s: &str) -> Result<char, ParseCodepointError> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => parse_codepoint(s),
+    }
+}
+
+#[derive(Debug)]
+enum CodepointsFileError {
+    Io(io::Error),
+    Parse { line: usize, err: ParseCodepointError },
+}
+
+impl fmt::Display for CodepointsFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Parse { line, err } => write!(f, "line {}: {}", line, err),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ParseCodepointError {
+    Malformed(String),
+    NotScalarValue(u32),
+    UnknownEscape(String),
+    NotSingleChar(String),
+}
+
+impl fmt::Display for ParseCodepointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(s) => write!(f, "invalid codepoint \"{}\"", s),
+            Self::NotScalarValue(codepoint) => {
+                write!(f, "U+{:04x} is not a Unicode scalar value", codepoint)
+            },
+            Self::UnknownEscape(s) => write!(f, "unknown escape sequence \"{}\"", s),
+            Self::NotSingleChar(s) => {
+                write!(f, "\"{}\" is not the UTF-8 encoding of a single character", s)
+            },
+        }
+    }
+}
+
+fn disable_columns(mut table: Table, headers: &[&str]) -> Table {
+    let indices = OutRow::headers()
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| headers.contains(&h.as_str()))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    // Disabling a column moves the columns after it to the left, so go from right to left to keep
+    // the remaining indices valid.
+    for i in indices.into_iter().rev() {
+        table = table.with(Disable::Column(i..=i));
+    }
+
+    table
+}
+
+#[derive(Tabled)]
+struct ExplainRow {
+    #[tabled(rename = "Property")]
+    property: &'static str,
+    #[tabled(rename = "Value")]
+    value: String,
+}
+
+impl ExplainRow {
+    fn new<T>(property: &'static str, value: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self { property, value: value.to_string() }
+    }
+}
+
+fn explain_rows(c: char) -> Vec<ExplainRow> {
+    let mut rows = vec![
+        ExplainRow::new("Character", c),
+        ExplainRow::new("Code", Codepoint(c)),
+        ExplainRow::new("UTF-8", Utf8Bytes::from_char(c)),
+        ExplainRow::new("UTF-16", Utf16Units::from_char(c)),
+    ];
+
+    let char_data = match char_data_why(c) {
+        Ok(char_data) => char_data,
+        Err(reason) => {
+            rows.push(ExplainRow::new("Name", format_args!("<{}>", reason)));
+            return rows;
+        },
+    };
+
+    let category = char_data.category();
+    let bidi = char_data.bidi_category();
+    let ccc = char_data.combining_class();
+
+    rows.push(ExplainRow::new("Name", char_data.name()));
+    rows.push(ExplainRow::new("Unicode 1.0 name", ExplainOptional(char_data.unicode_1_name())));
+    rows.push(ExplainRow::new(
+        "Category",
+        format_args!("{} ({})", category.full_name(), category.abbreviation())
+    ));
+    rows.push(ExplainRow::new(
+        "Bidi category",
+        format_args!("{} ({})", bidi.full_name(), bidi.abbreviation())
+    ));
+    rows.push(ExplainRow::new("Combining class", match ccc.name() {
+        Some(name) => format!("{} ({})", name, ccc.value()),
+        None => ccc.value().to_string(),
+    }));
+    rows.push(ExplainRow::new("Mirrored", YesNo(char_data.mirrored())));
+    rows.push(ExplainRow::new(
+        "Decomposition",
+        ExplainOptional(char_data.decomp_mapping().map(|decomp| decomp.ucd_form()))
+    ));
+    rows.push(ExplainRow::new(
+        "Decomposes to",
+        ExplainOptional(char_data.decomp_mapping().map(|decomp| DecompTargets(decomp.value())))
+    ));
+    rows.push(ExplainRow::new("Numeric value", ExplainOptional(char_data.numeric_value())));
+    rows.push(ExplainRow::new("Decimal digit", ExplainOptional(char_data.decimal_digit_value())));
+    rows.push(ExplainRow::new("Digit", ExplainOptional(char_data.digit_value())));
+    rows.push(ExplainRow::new("Uppercase", ExplainOptional(char_data.uppercase())));
+    rows.push(ExplainRow::new("Lowercase", ExplainOptional(char_data.lowercase())));
+    rows.push(ExplainRow::new("Titlecase", ExplainOptional(char_data.titlecase())));
+    rows.push(ExplainRow::new("Comment", ExplainOptional(char_data.comment())));
+    rows.push(ExplainRow::new("Properties", BinaryProperties(char_data)));
+
+    rows
+}
+
+#[derive(Tabled)]
+struct LineRow {
+    #[tabled(rename = "Line")]
+    line: usize,
+    #[tabled(rename = "Chars")]
+    chars: usize,
+    #[tabled(rename = "Invalid")]
+    invalid: usize,
+    #[tabled(rename = "Invisible")]
+    invisible: InvisibleChars,
+}
+
+impl LineRow {
+    fn new(unicode_data: &StaticUnicodeData, line: usize, bytes: &[u8]) -> Self {
+        let mut chars = 0;
+        let mut invalid = 0;
+        let mut invisible = Vec::new();
+
+        for res in bytes.decode_utf8() {
+            match res {
+                Ok(c) => {
+                    chars += 1;
+
+                    // Format characters (category Cf) are invisible but can change how the
+                    // surrounding text is displayed; this includes the bidirectional controls used
+                    // in "Trojan Source" attacks, zero-width characters and tag characters.
+                    let is_format = unicode_data
+                        .get(u32::from(c))
+                        .map(|char_data| char_data.category() == Category::Cf)
+                        .unwrap_or(false);
+
+                    if is_format {
+                        invisible.push(c);
+                    }
+                },
+                Err(_) => invalid += 1,
+            }
+        }
+
+        Self { line, chars, invalid, invisible: InvisibleChars(invisible) }
+    }
+
+    fn is_suspicious(&self) -> bool {
+        self.invalid > 0 || !self.invisible.0.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct InvisibleChars(Vec<char>);
+
+impl fmt::Display for InvisibleChars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chars = self.0.iter().copied();
+        match chars.next() {
+            Some(c) => {
+                write!(f, "{}", Codepoint(c))?;
+                for c in chars {
+                    write!(f, " {}", Codepoint(c))?;
+                }
+                Ok(())
+            },
+            None => f.write_str("-"),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct DiffRow<'a> {
+    #[tabled(rename = "Code")]
+    codepoint: DiffCodepoint,
+    #[tabled(rename = "Change")]
+    change: DiffChange,
+    #[tabled(rename = "Old name")]
+    old_name: Optional<&'a str>,
+    #[tabled(rename = "New name")]
+    new_name: Optional<&'a str>,
+    #[tabled(rename = "Old category")]
+    old_category: Optional<DisplayCategory>,
+    #[tabled(rename = "New category")]
+    new_category: Optional<DisplayCategory>,
+}
+
+/// Compares the name and category of every codepoint in the two sets of Unicode data. Changes to
+/// other properties are not reported.
+fn diff_rows<'a>(old: UnicodeData<'a>, new: UnicodeData<'a>, args: &Args) -> Vec<DiffRow<'a>> {
+    let display_category = |char_data: &CharData| DisplayCategory {
+        category: char_data.category(),
+        full_name: args.full_category_names,
+    };
+
+    (0..=u32::from(char::MAX))
+        .filter_map(|codepoint| {
+            let old_char_data = old.get(codepoint);
+            let new_char_data = new.get(codepoint);
 
-    let args = Args::parse();
+            let change = match (&old_char_data, &new_char_data) {
+                (None, None) => return None,
+                (None, Some(_)) => DiffChange::Added,
+                (Some(_), None) => DiffChange::Removed,
+                (Some(old_char_data), Some(new_char_data)) => {
+                    let renamed = old_char_data.name() != new_char_data.name();
+                    let recategorized = old_char_data.category() != new_char_data.category();
 
-    let input = {
-        let mut buf = Vec::<u8>::new();
-        let stdin = io::stdin();
-        let mut guard = stdin.lock();
-        guard.read_to_end(&mut buf)
-            .expect("failed to read stdin");
-        buf
-    };
+                    if !renamed && !recategorized {
+                        return None;
+                    }
 
-    let rows = input
-        .decode_utf8()
-        .map(|c| OutRow::from_char_result(&unicode_data, c, args.full_category_names));
+                    DiffChange::Changed { renamed, recategorized }
+                },
+            };
 
-    let table = Table::new(rows)
-        .with(Style::modern());
+            let old_name = old_char_data.as_ref().map(|char_data| char_data.name());
+            let new_name = new_char_data.as_ref().map(|char_data| char_data.name());
+            let old_category = old_char_data.as_ref().map(display_category);
+            let new_category = new_char_data.as_ref().map(display_category);
+
+            Some(DiffRow {
+                codepoint: DiffCodepoint(codepoint),
+                change,
+                old_name: old_name.into(),
+                new_name: new_name.into(),
+                old_category: old_category.into(),
+                new_category: new_category.into(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct DiffCodepoint(u32);
+
+impl fmt::Display for DiffCodepoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "U+{:04x}", self.0)
+    }
+}
+
+#[derive(Debug)]
+enum DiffChange {
+    Added,
+    Removed,
+    Changed { renamed: bool, recategorized: bool },
+}
 
-    println!("{}", table);
+impl fmt::Display for DiffChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => f.write_str("added"),
+            Self::Removed => f.write_str("removed"),
+            Self::Changed { renamed: true, recategorized: true } => {
+                f.write_str("renamed, recategorized")
+            },
+            Self::Changed { renamed: true, .. } => f.write_str("renamed"),
+            Self::Changed { .. } => f.write_str("recategorized"),
+        }
+    }
 }
 
 #[derive(Tabled)]
 struct OutRow {
     #[tabled(rename = "")]
-    display_char: CappedString<8>, 
+    display_char: DisplayChar,
     #[tabled(rename = "Code")]
     codepoint: Optional<Codepoint>,
+    #[tabled(rename = "Input")]
+    input_bytes: InputBytes,
     #[tabled(rename = "UTF-8")]
-    utf_8_bytes: Utf8Bytes,
+    utf_8_bytes: Optional<Utf8Bytes>,
+    #[tabled(rename = "UTF-16")]
+    utf_16_units: Optional<Utf16Units>,
+    #[tabled(rename = "UTF-16LE")]
+    utf_16_le_bytes: Optional<Utf16Bytes>,
+    #[tabled(rename = "UTF-16BE")]
+    utf_16_be_bytes: Optional<Utf16Bytes>,
+    #[tabled(rename = "UTF-32")]
+    utf_32_unit: Optional<Utf32Unit>,
+    #[tabled(rename = "Escape")]
+    escape: Optional<Escape>,
     #[tabled(rename = "Name")]
-    name: Optional<&'static str>,
+    name: Optional<DisplayName>,
     #[tabled(rename = "Category")]
     category: Optional<DisplayCategory>,
     #[tabled(rename = "Combining")]
     char_combining_class: Optional<DisplayCombiningClass>,
+    #[tabled(rename = "Bidi")]
+    bidi: Optional<DisplayBidiCategory>,
+    #[tabled(rename = "Decomposition")]
+    decomposition: Optional<ExplainOptional<UcdDecompMapping<'static>>>,
+    #[tabled(rename = "Numeric")]
+    numeric: Optional<ExplainOptional<&'static str>>,
+    #[tabled(rename = "Decimal")]
+    decimal: Optional<ExplainOptional<u8>>,
+    #[tabled(rename = "Digit")]
+    digit: Optional<ExplainOptional<u8>>,
+    #[tabled(rename = "Mirrored")]
+    mirrored: Optional<YesNo>,
+    #[tabled(rename = "Unicode 1.0 Name")]
+    unicode_1_name: Optional<ExplainOptional<&'static str>>,
+    #[tabled(rename = "Comment")]
+    comment: Optional<ExplainOptional<&'static str>>,
 }
 
 impl OutRow {
     fn from_char_result(
         unicode_data: &StaticUnicodeData,
-        c: Result<char, Utf8Error>,
-        full_category_names: bool
+        c: Result<char, InputError>,
+        encoding: InputEncoding,
+        args: &Args
     ) -> Self
     {
         match c {
-            Ok(c) => Self::from_good_char(unicode_data, c, full_category_names),
-            Err(err) => Self::from_bad_char(err),
+            Ok(c) => Self::from_good_char(unicode_data, c, encoding, args),
+            Err(err) => Self::from_bad_char(err, args),
         }
     }
 
     fn from_good_char(
         unicode_data: &StaticUnicodeData,
         c: char,
-        full_category_names: bool
+        encoding: InputEncoding,
+        args: &Args
     ) -> Self
     {
         let mut name = Optional::None;
         let mut category = Optional::None;
         let mut char_combining_class = Optional::None;
+        let mut bidi = Optional::None;
+        let mut decomposition = Optional::None;
+        let mut numeric = Optional::None;
+        let mut decimal = Optional::None;
+        let mut digit = Optional::None;
+        let mut mirrored = Optional::None;
+        let mut unicode_1_name = Optional::None;
+        let mut comment = Optional::None;
         
         let mut combining = false;
 
         if let Some(char_data) = unicode_data.get(u32::from(c)) {
-            name = Optional::Some(char_data.name());
+            name = Optional::Some(DisplayName {
+                name: char_data.name(),
+                max_width: args.name_width_limit(),
+            });
             category = Optional::Some(DisplayCategory {
                 category: char_data.category(),
-                full_name: full_category_names,
+                full_name: args.full_category_names,
             });
 
             let ccc = char_data.combining_class();
             char_combining_class = Optional::Some(DisplayCombiningClass { ccc });
-            combining = ccc.is_combining();
-        }
+            combining = char_data.is_mark() || ccc.is_combining();
 
-        let display_char = {
-            let mut buf = CappedString::empty();
-            if combining {
-                buf.push_truncating('\u{25cc}');
-            }
-            buf.push_truncating(c);
-            buf
-        };
+            bidi = Optional::Some(DisplayBidiCategory {
+                bidi: char_data.bidi_category(),
+                full_name: args.full_category_names,
+            });
+            decomposition = Optional::Some(ExplainOptional(
+                char_data.decomp_mapping().map(|decomp| decomp.ucd_form())
+            ));
+            numeric = Optional::Some(ExplainOptional(char_data.numeric_value()));
+            decimal = Optional::Some(ExplainOptional(char_data.decimal_digit_value()));
+            digit = Optional::Some(ExplainOptional(char_data.digit_value()));
+            mirrored = Optional::Some(YesNo(char_data.mirrored()));
+            unicode_1_name = Optional::Some(ExplainOptional(char_data.unicode_1_name()));
+            comment = Optional::Some(ExplainOptional(char_data.comment()));
+        }
 
         Self {
-            display_char,
+            display_char: DisplayChar { c, placeholder: combining },
             codepoint: Optional::Some(Codepoint(c)),
-            utf_8_bytes: Utf8Bytes::from_char(c),
+            input_bytes: InputBytes::encode(c, encoding),
+            utf_8_bytes: Optional::Some(Utf8Bytes::from_char(c)),
+            utf_16_units: Optional::Some(Utf16Units::from_char(c)),
+            utf_16_le_bytes: Optional::Some(Utf16Bytes {
+                units: Utf16Units::from_char(c),
+                big_endian: false,
+            }),
+            utf_16_be_bytes: Optional::Some(Utf16Bytes {
+                units: Utf16Units::from_char(c),
+                big_endian: true,
+            }),
+            utf_32_unit: Optional::Some(Utf32Unit(c)),
+            escape: Optional::Some(Escape {
+                c,
+                style: args.escape.unwrap_or(EscapeStyle::Rust),
+            }),
             name,
             category,
             char_combining_class,
+            bidi,
+            decomposition,
+            numeric,
+            decimal,
+            digit,
+            mirrored,
+            unicode_1_name,
+            comment,
         }
     }
 
-    fn from_bad_char(err: Utf8Error) -> Self {
-        let (bad_bytes, _num_bad_bytes, num_consumed_bad_bytes) = err.into_parts();
+    fn from_bad_char(err: InputError, args: &Args) -> Self {
+        // Only invalid UTF-8 is shown in the UTF-8 column; invalid sequences in other encodings are
+        // only shown in the input column.
+        let is_utf_8 = matches!(err, InputError::Utf8(_));
+        let input_bytes = err.into_bad_bytes();
+        let utf_8_bytes = match is_utf_8 {
+            true => Optional::Some(Utf8Bytes { buf: input_bytes.buf, len: input_bytes.len }),
+            false => Optional::None,
+        };
 
         Self {
-            display_char: CappedString::new_truncating("\u{fffd}"),
+            display_char: DisplayChar { c: '\u{fffd}', placeholder: false },
             codepoint: Optional::None,
-            utf_8_bytes: Utf8Bytes {
-                buf: bad_bytes,
-                len: num_consumed_bad_bytes,
-            },
-            name: Optional::Some("<invalid>"),
+            input_bytes,
+            utf_8_bytes,
+            utf_16_units: Optional::None,
+            utf_16_le_bytes: Optional::None,
+            utf_16_be_bytes: Optional::None,
+            utf_32_unit: Optional::None,
+            escape: Optional::None,
+            name: Optional::Some(DisplayName {
+                name: "<invalid>",
+                max_width: args.name_width_limit(),
+            }),
             category: Optional::None,
             char_combining_class: Optional::None,
+            bidi: Optional::None,
+            decomposition: Optional::None,
+            numeric: Optional::None,
+            decimal: Optional::None,
+            digit: Optional::None,
+            mirrored: Optional::None,
+            unicode_1_name: Optional::None,
+            comment: Optional::None,
+        }
+    }
+}
+
+fn write_ndjson_row(
+    out: &mut dyn Write,
+    unicode_data: &StaticUnicodeData,
+    c: Result<char, InputError>,
+    encoding: InputEncoding,
+    args: &Args
+) -> io::Result<()>
+{
+    match c {
+        Ok(c) => {
+            let mut buf = [0u8; 4];
+            write!(out, "{{\"char\":{},\"codepoint\":{},\"utf8\":{}",
+                JsonStr(c.encode_utf8(&mut buf)),
+                u32::from(c),
+                JsonBytes(Utf8Bytes::from_char(c).bytes()))?;
+
+            if encoding != InputEncoding::Utf8 {
+                write!(out, ",\"input_bytes\":{}",
+                    JsonBytes(InputBytes::encode(c, encoding).bytes()))?;
+            }
+
+            if let Some(style) = args.escape {
+                write!(out, ",\"escape\":{}", JsonStr(&Escape { c, style }.to_string()))?;
+            }
+
+            match unicode_data.get(u32::from(c)) {
+                Some(char_data) => {
+                    let category = DisplayCategory {
+                        category: char_data.category(),
+                        full_name: args.full_category_names,
+                    };
+                    write!(out, ",\"name\":{},\"category\":{},\"combining_class\":{}",
+                        JsonStr(char_data.name()),
+                        JsonStr(&category.to_string()),
+                        char_data.combining_class().value())?;
+                },
+                None => {
+                    write!(out, ",\"name\":null,\"category\":null,\"combining_class\":null")?;
+                },
+            }
+
+            writeln!(out, "}}")
+        },
+
+        Err(err) => {
+            let error = err.to_string();
+            writeln!(out,
+                "{{\"char\":null,\"codepoint\":null,\"input_bytes\":{},\"error\":{}}}",
+                JsonBytes(err.into_bad_bytes().bytes()),
+                JsonStr(&error))
+        },
+    }
+}
+
+/// A string written as a quoted JSON string literal.
+struct JsonStr<'a>(&'a str);
+
+impl<'a> fmt::Display for JsonStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                '\u{0}'..='\u{1f}' => write!(f, "\\u{:04x}", u32::from(c))?,
+                c => write!(f, "{}", c)?,
+            }
         }
+        f.write_str("\"")
+    }
+}
+
+/// A byte slice written as a JSON array of numbers.
+struct JsonBytes<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for JsonBytes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, b) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", b)?;
+        }
+        f.write_str("]")
     }
 }
 
@@ -132,6 +1495,15 @@ enum Optional<T> {
     None,
 }
 
+impl<T> From<Option<T>> for Optional<T> {
+    fn from(x: Option<T>) -> Self {
+        match x {
+            Some(x) => Self::Some(x),
+            None => Self::None,
+        }
+    }
+}
+
 impl<T> fmt::Display for Optional<T>
 where
     T: fmt::Display,
@@ -144,6 +1516,34 @@ where
     }    
 }
 
+/// A single character as displayed in the first column, so that each row shows exactly one
+/// character regardless of how many bytes it takes to encode.
+#[derive(Debug)]
+struct DisplayChar {
+    c: char,
+    /// Whether to display a dotted circle before the character for it to combine with, rather than
+    /// having it combine with the table border.
+    placeholder: bool,
+}
+
+impl fmt::Display for DisplayChar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.placeholder {
+            write!(f, "\u{25cc}")?;
+        }
+
+        // Control characters would break the table layout, so display them using the symbols from
+        // the Control Pictures block instead.
+        match u32::from(self.c) {
+            codepoint @ 0x00..=0x1f => {
+                write!(f, "{}", char::from_u32(0x2400 + codepoint).unwrap())
+            },
+            0x7f => write!(f, "\u{2421}"),
+            _ => write!(f, "{}", self.c),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Codepoint(char);
 
@@ -153,6 +1553,26 @@ impl fmt::Display for Codepoint {
     }
 }
 
+#[derive(Debug)]
+struct Escape {
+    c: char,
+    style: EscapeStyle,
+}
+
+impl fmt::Display for Escape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let codepoint = self.c as u32;
+        match self.style {
+            EscapeStyle::Rust => write!(f, "\\u{{{:x}}}", codepoint),
+            EscapeStyle::Python if codepoint > 0xffff => write!(f, "\\U{:08x}", codepoint),
+            EscapeStyle::Python => write!(f, "\\u{:04x}", codepoint),
+            EscapeStyle::Html => write!(f, "&#x{:x};", codepoint),
+            EscapeStyle::HtmlDec => write!(f, "&#{};", codepoint),
+            EscapeStyle::Decimal => write!(f, "{}", codepoint),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Utf8Bytes {
     buf: [u8; 4],
@@ -174,17 +1594,226 @@ impl Utf8Bytes {
 
 impl fmt::Display for Utf8Bytes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut bytes = self.bytes().iter().copied();
-        if let Some(b) = bytes.next() {
-            write!(f, "0x{:02x}", b)?;
-            for b in bytes {
-                write!(f, " 0x{:02x}", b)?;
+        write_hex_bytes(f, self.bytes().iter().copied())
+    }
+}
+
+/// The bytes of the input that a character, or an invalid sequence, was decoded from.
+#[derive(Debug)]
+struct InputBytes {
+    buf: [u8; 4],
+    len: usize,
+}
+
+impl InputBytes {
+    /// Encodes a character that was decoded from the input back into the input's encoding.
+    fn encode(c: char, encoding: InputEncoding) -> Self {
+        let mut buf = [0u8; 4];
+
+        let len = match encoding {
+            InputEncoding::Utf8 | InputEncoding::Auto => c.encode_utf8(&mut buf).len(),
+            InputEncoding::Utf16le | InputEncoding::Utf16be => {
+                let mut units = [0u16; 2];
+                let units = c.encode_utf16(&mut units);
+                for (unit, chunk) in units.iter().zip(buf.chunks_exact_mut(2)) {
+                    chunk.copy_from_slice(&match encoding {
+                        InputEncoding::Utf16be => unit.to_be_bytes(),
+                        _ => unit.to_le_bytes(),
+                    });
+                }
+                units.len() * 2
+            },
+            InputEncoding::Utf32le => {
+                buf = u32::from(c).to_le_bytes();
+                4
+            },
+            InputEncoding::Utf32be => {
+                buf = u32::from(c).to_be_bytes();
+                4
+            },
+            InputEncoding::Latin1 => {
+                buf[0] = u8::try_from(c)
+                    .expect("character decoded from Latin-1 should fit in a byte");
+                1
+            },
+            InputEncoding::Cp1252 => {
+                buf[0] = char_to_cp1252(c)
+                    .expect("character decoded from Windows-1252 should be in Windows-1252");
+                1
+            },
+        };
+
+        Self { buf, len }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl fmt::Display for InputBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex_bytes(f, self.bytes().iter().copied())
+    }
+}
+
+/// Writes each byte in hex, separated by spaces.
+fn write_hex_bytes<I>(f: &mut fmt::Formatter<'_>, bytes: I) -> fmt::Result
+where
+    I: IntoIterator<Item = u8>,
+{
+    let mut bytes = bytes.into_iter();
+    if let Some(b) = bytes.next() {
+        write!(f, "0x{:02x}", b)?;
+        for b in bytes {
+            write!(f, " 0x{:02x}", b)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Utf16Units {
+    buf: [u16; 2],
+    len: usize,
+}
+
+impl Utf16Units {
+    fn from_char(c: char) -> Self {
+        let mut buf = [0u16; 2];
+        let len = c.encode_utf16(&mut buf).len();
+        Self { buf, len }
+    }
+}
+
+impl fmt::Display for Utf16Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut units = self.buf[..self.len].iter().copied();
+        if let Some(unit) = units.next() {
+            write!(f, "0x{:04x}", unit)?;
+            for unit in units {
+                write!(f, " 0x{:04x}", unit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The bytes of a UTF-16 encoded character, in the given byte order.
+#[derive(Debug)]
+struct Utf16Bytes {
+    units: Utf16Units,
+    big_endian: bool,
+}
+
+impl fmt::Display for Utf16Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.units.buf[..self.units.len]
+            .iter()
+            .flat_map(|unit| {
+                if self.big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() }
+            });
+        write_hex_bytes(f, bytes)
+    }
+}
+
+#[derive(Debug)]
+struct Utf32Unit(char);
+
+impl fmt::Display for Utf32Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:08x}", u32::from(self.0))
+    }
+}
+
+struct ExplainOptional<T>(Option<T>);
+
+impl<T> fmt::Display for ExplainOptional<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(x) => fmt::Display::fmt(x, f),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+/// Each character of a decomposition mapping, written as its codepoint followed by its name.
+struct DecompTargets(&'static str);
+
+impl fmt::Display for DecompTargets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, c) in self.0.chars().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            match char_data_why(c) {
+                Ok(char_data) => write!(f, "{} {}", Codepoint(c), char_data.name())?,
+                Err(reason) => write!(f, "{} <{}>", Codepoint(c), reason)?,
             }
         }
+
         Ok(())
     }
 }
 
+struct YesNo(bool);
+
+impl fmt::Display for YesNo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0 { "yes" } else { "no" })
+    }
+}
+
+struct BinaryProperties(CharData<'static>);
+
+impl fmt::Display for BinaryProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self.0
+            .boolean_properties()
+            .filter(|&(_, has_property)| has_property)
+            .map(|(name, _)| name);
+
+        match names.next() {
+            Some(name) => {
+                f.write_str(name)?;
+                for name in names {
+                    write!(f, ", {}", name)?;
+                }
+                Ok(())
+            },
+            None => f.write_str("-"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DisplayName {
+    name: &'static str,
+    max_width: Option<usize>,
+}
+
+impl fmt::Display for DisplayName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max_width {
+            Some(max_width) if self.name.chars().count() > max_width => {
+                // Leave space for the ellipsis so the truncated name is at most `max_width` wide.
+                let prefix_len = self.name
+                    .char_indices()
+                    .nth(max_width.saturating_sub(1))
+                    .map(|(i, _)| i)
+                    .unwrap_or(self.name.len());
+
+                write!(f, "{}\u{2026}", &self.name[..prefix_len])
+            },
+            _ => f.write_str(self.name),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct DisplayCategory {
     category: Category,
@@ -201,6 +1830,22 @@ impl fmt::Display for DisplayCategory {
     }
 }
 
+#[derive(Debug)]
+struct DisplayBidiCategory {
+    bidi: BidiCategory,
+    full_name: bool,
+}
+
+impl fmt::Display for DisplayBidiCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.full_name {
+            write!(f, "{}", self.bidi.full_name())
+        } else {
+            write!(f, "{}", self.bidi.abbreviation())
+        }
+    }
+}
+
 struct DisplayCombiningClass {
     ccc: CombiningClass,
 }
@@ -209,7 +1854,410 @@ impl fmt::Display for DisplayCombiningClass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.ccc.name() {
             Some(name) => write!(f, "{}", name),
-            None => write!(f, "{}", self.ccc.0),
+            None => write!(f, "{}", self.ccc.value()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use tabled::Table;
+
+    use super::{
+        decode_input,
+        disable_columns,
+        explain_rows,
+        parse_codepoint,
+        parse_hex_bytes,
+        style_table,
+        table_width,
+        write_ndjson_row,
+        Args,
+        InputBytes,
+        InputEncoding,
+        InputError,
+        OutRow,
+        ParseCodepointError,
+        StaticUnicodeData,
+    };
+
+    /// Decodes the bytes, replacing each invalid sequence with the input bytes it was made of.
+    fn decode(
+        bytes: &[u8],
+        encoding: InputEncoding
+    ) -> (InputEncoding, Vec<Result<char, Vec<u8>>>)
+    {
+        let (encoding, chars) = decode_input(bytes.iter().copied(), encoding);
+        let chars = chars
+            .map(|c| c.map_err(|err| err.into_bad_bytes().bytes().to_vec()))
+            .collect();
+        (encoding, chars)
+    }
+
+    fn explain_value(c: char, property: &str) -> String {
+        explain_rows(c)
+            .into_iter()
+            .find(|row| row.property == property)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn test_explain_digits() {
+        assert_eq!(explain_value('5', "Decimal digit"), "5");
+        assert_eq!(explain_value('5', "Digit"), "5");
+        assert_eq!(explain_value('\u{b2}', "Decimal digit"), "-");
+        assert_eq!(explain_value('\u{b2}', "Digit"), "2");
+        assert_eq!(explain_value('A', "Decimal digit"), "-");
+        assert_eq!(explain_value('A', "Digit"), "-");
+    }
+
+    #[test]
+    fn test_args_debug_assert() {
+        use clap::CommandFactory;
+        Args::command().debug_assert();
+
+        // The other modes are handled before --count, so it would be ignored if combined with them.
+        for other in [
+            &["--codepoints-file", "x"][..],
+            &["--explain", "x"],
+            &["--diff", "x", "y"],
+            &["--by-line"],
+            &["--sort", "codepoint"],
+        ] {
+            let err = Args::try_parse_from(["utfdump", "--count"].iter().chain(other))
+                .err()
+                .unwrap();
+            assert_eq!(err.kind(), clap::ErrorKind::ArgumentConflict, "{:?}", other);
+        }
+
+        assert!(Args::try_parse_from(["utfdump", "--count", "abc"]).is_ok());
+    }
+
+    /// Writes a single ndjson row, parsing `args` as the command-line arguments.
+    fn ndjson_row(c: Result<char, InputError>, encoding: InputEncoding, args: &[&str]) -> String {
+        let unicode_data = StaticUnicodeData::new().unwrap();
+        let args = Args::parse_from(["utfdump", "--format", "ndjson"].iter().chain(args));
+
+        let mut out = Vec::new();
+        write_ndjson_row(&mut out, &unicode_data, c, encoding, &args).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_ndjson_rows() {
+        assert_eq!(
+            ndjson_row(Ok('a'), InputEncoding::Utf8, &[]),
+            "{\"char\":\"a\",\"codepoint\":97,\"utf8\":[97],\"name\":\"LATIN SMALL LETTER A\",\
+                \"category\":\"Ll\",\"combining_class\":0}\n"
+        );
+        assert_eq!(
+            ndjson_row(Ok('\u{e9}'), InputEncoding::Utf16le, &["--escape", "python", "-f"]),
+            "{\"char\":\"\u{e9}\",\"codepoint\":233,\"utf8\":[195,169],\"input_bytes\":[233,0],\
+                \"escape\":\"\\\\u00e9\",\"name\":\"LATIN SMALL LETTER E WITH ACUTE\",\
+                \"category\":\"Letter, Lowercase\",\"combining_class\":0}\n"
+        );
+        assert_eq!(
+            ndjson_row(Ok('\u{378}'), InputEncoding::Utf8, &[]),
+            "{\"char\":\"\u{378}\",\"codepoint\":888,\"utf8\":[205,184],\"name\":null,\
+                \"category\":null,\"combining_class\":null}\n"
+        );
+
+        let (_, mut chars) = decode_input(b"\xe2\x80".iter().copied(), InputEncoding::Utf8);
+        assert_eq!(
+            ndjson_row(chars.next().unwrap(), InputEncoding::Utf8, &[]),
+            "{\"char\":null,\"codepoint\":null,\"input_bytes\":[226,128],\
+                \"error\":\"truncated sequence\"}\n"
+        );
+
+        let err = InputError::UnpairedSurrogate { unit: 0xd800, bytes: [0x00, 0xd8] };
+        assert_eq!(
+            ndjson_row(Err(err), InputEncoding::Utf16le, &[]),
+            "{\"char\":null,\"codepoint\":null,\"input_bytes\":[0,216],\
+                \"error\":\"unpaired surrogate 0xd800\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_ndjson_escaping() {
+        assert_eq!(
+            ndjson_row(Ok('"'), InputEncoding::Utf8, &[]),
+            "{\"char\":\"\\\"\",\"codepoint\":34,\"utf8\":[34],\"name\":\"QUOTATION MARK\",\
+                \"category\":\"Po\",\"combining_class\":0}\n"
+        );
+        assert_eq!(
+            ndjson_row(Ok('\\'), InputEncoding::Utf8, &[]),
+            "{\"char\":\"\\\\\",\"codepoint\":92,\"utf8\":[92],\"name\":\"REVERSE SOLIDUS\",\
+                \"category\":\"Po\",\"combining_class\":0}\n"
+        );
+
+        for codepoint in 0x00..=0x1f {
+            let c = char::from_u32(codepoint).unwrap();
+            let escaped = match c {
+                '\n' => "\\n".to_owned(),
+                '\r' => "\\r".to_owned(),
+                '\t' => "\\t".to_owned(),
+                _ => format!("\\u{:04x}", codepoint),
+            };
+            assert_eq!(
+                ndjson_row(Ok(c), InputEncoding::Utf8, &[]),
+                format!(
+                    "{{\"char\":\"{}\",\"codepoint\":{},\"utf8\":[{}],\"name\":\"<control>\",\
+                        \"category\":\"Cc\",\"combining_class\":0}}\n",
+                    escaped,
+                    codepoint,
+                    codepoint
+                )
+            );
+        }
+
+        // DEL and characters outside ASCII are valid in JSON strings, so they are not escaped.
+        assert!(ndjson_row(Ok('\u{7f}'), InputEncoding::Utf8, &[])
+            .starts_with("{\"char\":\"\u{7f}\",\"codepoint\":127,"));
+    }
+
+    #[test]
+    fn test_table_width() {
+        let unicode_data = StaticUnicodeData::new().unwrap();
+
+        let inputs = [
+            // Wide characters, a combining mark, a control character and an invalid sequence.
+            &b"a\xf0\x9f\x98\x80\xe6\xbc\xa2e\xcc\x81\n\xc3\xbf\xff"[..],
+            // A wide character is the widest in its column only if there is no combining mark.
+            b"a\xf0\x9f\x98\x80",
+        ];
+
+        let arg_lists = [
+            &[][..],
+            &["-f"],
+            &["--no-header"],
+            &["--escape", "python", "--column", "utf16,bidi,numeric"],
+            &["--all-columns"],
+            &["--max-name-width", "8"],
+        ];
+
+        let cases = inputs.iter().flat_map(|input| arg_lists.map(|args| (input, args)));
+
+        for (input, arg_list) in cases {
+            let args = Args::parse_from(["utfdump"].iter().chain(arg_list));
+            let hidden_columns = if args.all_columns { &[][..] } else { &["Escape", "Input"] };
+
+            let (_, chars) = decode_input(input.iter().copied(), InputEncoding::Utf8);
+            let rows = chars
+                .map(|c| OutRow::from_char_result(&unicode_data, c, InputEncoding::Utf8, &args))
+                .collect::<Vec<_>>();
+
+            let rendered = style_table(disable_columns(Table::new(&rows), hidden_columns), &args)
+                .to_string();
+            let rendered_width = rendered.lines().next().unwrap().chars().count();
+
+            assert_eq!(table_width(&rows, hidden_columns, &args), rendered_width, "{:?}", arg_list);
+        }
+    }
+
+    #[test]
+    fn test_parse_escaped_codepoint() {
+        assert_eq!(parse_codepoint("\\u{1F600}"), Ok('\u{1f600}'));
+        assert_eq!(parse_codepoint("\\u{e9}"), Ok('\u{e9}'));
+        assert_eq!(parse_codepoint("\\u1F600"), Ok('\u{1f600}'));
+        assert_eq!(parse_codepoint("\\U0001F600"), Ok('\u{1f600}'));
+        assert_eq!(parse_codepoint("\\xC3\\xA9"), Ok('\u{e9}'));
+        assert_eq!(parse_codepoint("\\x41"), Ok('A'));
+
+        assert_eq!(
+            parse_codepoint("\\xC3"),
+            Err(ParseCodepointError::NotSingleChar("\\xC3".to_owned()))
+        );
+        assert_eq!(
+            parse_codepoint("\\xC3\\xA9\\x41"),
+            Err(ParseCodepointError::NotSingleChar("\\xC3\\xA9\\x41".to_owned()))
+        );
+        assert_eq!(
+            parse_codepoint("\\xC"),
+            Err(ParseCodepointError::Malformed("\\xC".to_owned()))
+        );
+        assert_eq!(
+            parse_codepoint("\\q"),
+            Err(ParseCodepointError::UnknownEscape("\\q".to_owned()))
+        );
+        assert_eq!(parse_codepoint("\\u{D800}"), Err(ParseCodepointError::NotScalarValue(0xd800)));
+        assert_eq!(
+            parse_codepoint("\\U00110000"),
+            Err(ParseCodepointError::NotScalarValue(0x110000))
+        );
+
+        // Without the closing brace, this falls back to the `\u` form, so the brace is read as
+        // part of the hex digits.
+        assert_eq!(
+            parse_codepoint("\\u{1F600"),
+            Err(ParseCodepointError::Malformed("\\u{1F600".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_hex_bytes() {
+        let parse = |s| parse_hex_bytes(s).map_err(|err| err.to_string());
+
+        assert_eq!(parse(""), Ok(vec![]));
+        assert_eq!(parse(" \n\t"), Ok(vec![]));
+        assert_eq!(parse("e2 80 8b"), Ok(vec![0xe2, 0x80, 0x8b]));
+        assert_eq!(parse("E2\n80\t8B\n"), Ok(vec![0xe2, 0x80, 0x8b]));
+        assert_eq!(parse("0xe2 0X80 8b"), Ok(vec![0xe2, 0x80, 0x8b]));
+        assert_eq!(parse("e2808b 0x41"), Ok(vec![0xe2, 0x80, 0x8b, 0x41]));
+
+        assert_eq!(parse("e28"), Err("invalid hex bytes \"e28\"".to_owned()));
+        assert_eq!(parse("41 0x4"), Err("invalid hex bytes \"0x4\"".to_owned()));
+        assert_eq!(parse("41 g1"), Err("invalid hex bytes \"g1\"".to_owned()));
+        assert_eq!(parse("+f"), Err("invalid hex bytes \"+f\"".to_owned()));
+        assert_eq!(parse("0x"), Err("invalid hex bytes \"0x\"".to_owned()));
+        assert_eq!(parse("41,42"), Err("invalid hex bytes \"41,42\"".to_owned()));
+        assert_eq!(parse("0x0x41"), Err("invalid hex bytes \"0x0x41\"".to_owned()));
+    }
+
+    #[test]
+    fn test_utf16_decoder() {
+        assert_eq!(
+            decode(b"a\x00\xe9\x00\x3d\xd8\x00\xde", InputEncoding::Utf16le).1,
+            vec![Ok('a'), Ok('\u{e9}'), Ok('\u{1f600}')]
+        );
+        assert_eq!(
+            decode(b"\x00a\x00\xe9\xd8\x3d\xde\x00", InputEncoding::Utf16be).1,
+            vec![Ok('a'), Ok('\u{e9}'), Ok('\u{1f600}')]
+        );
+    }
+
+    #[test]
+    fn test_utf16_decoder_invalid() {
+        // A high surrogate followed by something other than a low surrogate is unpaired, and the
+        // code unit after it is decoded on its own.
+        assert_eq!(
+            decode(b"\x3d\xd8a\x00", InputEncoding::Utf16le).1,
+            vec![Err(vec![0x3d, 0xd8]), Ok('a')]
+        );
+        assert_eq!(
+            decode(b"\x3d\xd8\x3d\xd8\x00\xde", InputEncoding::Utf16le).1,
+            vec![Err(vec![0x3d, 0xd8]), Ok('\u{1f600}')]
+        );
+        assert_eq!(
+            decode(b"\x00\xdea\x00", InputEncoding::Utf16le).1,
+            vec![Err(vec![0x00, 0xde]), Ok('a')]
+        );
+        assert_eq!(
+            decode(b"a\x00\x3d\xd8", InputEncoding::Utf16le).1,
+            vec![Ok('a'), Err(vec![0x3d, 0xd8])]
+        );
+        assert_eq!(
+            decode(b"\xd8\x3d\x00", InputEncoding::Utf16be).1,
+            vec![Err(vec![0xd8, 0x3d]), Err(vec![0x00])]
+        );
+        assert_eq!(
+            decode(b"a\x00b", InputEncoding::Utf16le).1,
+            vec![Ok('a'), Err(vec![b'b'])]
+        );
+    }
+
+    #[test]
+    fn test_utf32_decoder() {
+        assert_eq!(
+            decode(b"a\x00\x00\x00\x00\xf6\x01\x00", InputEncoding::Utf32le).1,
+            vec![Ok('a'), Ok('\u{1f600}')]
+        );
+        assert_eq!(
+            decode(b"\x00\x00\x00a\x00\x01\xf6\x00", InputEncoding::Utf32be).1,
+            vec![Ok('a'), Ok('\u{1f600}')]
+        );
+    }
+
+    #[test]
+    fn test_utf32_decoder_invalid() {
+        let decode_errors = |bytes: &[u8], encoding| {
+            let (_, chars) = decode_input(bytes.iter().copied(), encoding);
+            chars
+                .map(|c| c.map_err(|err| err.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            decode_errors(b"\x00\x00\x11\x00a\x00\x00\x00", InputEncoding::Utf32le),
+            vec![Err("code unit 0x00110000 out of range".to_owned()), Ok('a')]
+        );
+        assert_eq!(
+            decode_errors(b"\xff\xff\xff\xff", InputEncoding::Utf32be),
+            vec![Err("code unit 0xffffffff out of range".to_owned())]
+        );
+        assert_eq!(
+            decode_errors(b"\x00\x00\xd8\x00", InputEncoding::Utf32be),
+            vec![Err("surrogate code unit 0x0000d800".to_owned())]
+        );
+        assert_eq!(
+            decode_errors(b"\xff\xdf\x00\x00", InputEncoding::Utf32le),
+            vec![Err("surrogate code unit 0x0000dfff".to_owned())]
+        );
+
+        assert_eq!(
+            decode(b"\x00\x00\x11\x00\x00\xd8\x00\x00", InputEncoding::Utf32le).1,
+            vec![Err(vec![0x00, 0x00, 0x11, 0x00]), Err(vec![0x00, 0xd8, 0x00, 0x00])]
+        );
+
+        // Trailing bytes which are too few to make up a code unit are reported together.
+        for len in 1..4 {
+            let mut bytes = b"a\x00\x00\x00".to_vec();
+            bytes.extend_from_slice(&[0x62, 0x00, 0x00][..len]);
+            assert_eq!(
+                decode(&bytes, InputEncoding::Utf32le).1,
+                vec![Ok('a'), Err(bytes[4..].to_vec())]
+            );
+            assert_eq!(
+                decode_errors(&bytes[4..], InputEncoding::Utf32be),
+                vec![Err("incomplete code unit".to_owned())]
+            );
         }
     }
+
+    #[test]
+    fn test_auto_detect_encoding() {
+        let detect = |bytes: &[u8]| decode(bytes, InputEncoding::Auto);
+
+        // The UTF-32LE byte order mark starts with the UTF-16LE one.
+        assert_eq!(
+            detect(b"\xff\xfe\x00\x00a\x00\x00\x00"),
+            (InputEncoding::Utf32le, vec![Ok('\u{feff}'), Ok('a')])
+        );
+        assert_eq!(
+            detect(b"\x00\x00\xfe\xff\x00\x00\x00a"),
+            (InputEncoding::Utf32be, vec![Ok('\u{feff}'), Ok('a')])
+        );
+        assert_eq!(
+            detect(b"\xff\xfea\x00b\x00"),
+            (InputEncoding::Utf16le, vec![Ok('\u{feff}'), Ok('a'), Ok('b')])
+        );
+        assert_eq!(
+            detect(b"\xfe\xff\x00a"),
+            (InputEncoding::Utf16be, vec![Ok('\u{feff}'), Ok('a')])
+        );
+        assert_eq!(
+            detect(b"\xff\xfe"),
+            (InputEncoding::Utf16le, vec![Ok('\u{feff}')])
+        );
+        assert_eq!(detect(b"ab"), (InputEncoding::Utf8, vec![Ok('a'), Ok('b')]));
+        assert_eq!(detect(b"a\xff"), (InputEncoding::Utf8, vec![Ok('a'), Err(vec![0xff])]));
+        assert_eq!(detect(b""), (InputEncoding::Utf8, vec![]));
+    }
+
+    #[test]
+    fn test_input_bytes() {
+        let encode = |c, encoding| InputBytes::encode(c, encoding).bytes().to_vec();
+
+        assert_eq!(encode('\u{e9}', InputEncoding::Utf8), [0xc3, 0xa9]);
+        assert_eq!(encode('\u{1f600}', InputEncoding::Utf16le), [0x3d, 0xd8, 0x00, 0xde]);
+        assert_eq!(encode('\u{1f600}', InputEncoding::Utf16be), [0xd8, 0x3d, 0xde, 0x00]);
+        assert_eq!(encode('a', InputEncoding::Utf16be), [0x00, b'a']);
+        assert_eq!(encode('a', InputEncoding::Utf32le), [b'a', 0x00, 0x00, 0x00]);
+        assert_eq!(encode('a', InputEncoding::Utf32be), [0x00, 0x00, 0x00, b'a']);
+        assert_eq!(encode('\u{e9}', InputEncoding::Latin1), [0xe9]);
+        assert_eq!(encode('\u{20ac}', InputEncoding::Cp1252), [0x80]);
+        assert_eq!(encode('\u{81}', InputEncoding::Cp1252), [0x81]);
+        assert_eq!(encode('\u{e9}', InputEncoding::Cp1252), [0xe9]);
+    }
 }
@@ -246,7 +246,7 @@ def encode_char_data(
         decimal_digit = 0xf
     if digit is None:
         digit = 0xf
-    digit_vals = (decimal_digit & 0xf) | ((digit << 4) & 0xf)
+    digit_vals = (decimal_digit & 0xf) | ((digit & 0xf) << 4)
     encoded.extend(digit_vals.to_bytes(length=1, byteorder='little', signed=False))
 
     assert len(encoded) == 28
@@ -310,10 +310,12 @@ for row in rows:
         assert cell_name.startswith('<') and cell_name.endswith(', Last>')
         groups.append(Group(GroupKind.USE_PREV_VALUE, prev_code + 1, code))
 
-    # If there is a gap between the previous codepoint and this codepoint, add a "no value" group
-    # to the list of groups to indicate the gap.
-    elif prev_code is not None and code > prev_code + 1:
-        groups.append(Group(GroupKind.NO_VALUE, prev_code + 1, code - 1))
+    # If there is a gap between the previous codepoint (or U+0000, for the first row) and this
+    # codepoint, add a "no value" group to the list of groups to indicate the gap.
+    else:
+        gap_start = 0 if prev_code is None else prev_code + 1
+        if code > gap_start:
+            groups.append(Group(GroupKind.NO_VALUE, gap_start, code - 1))
     
     prev_code = code
 
@@ -1,20 +1,51 @@
-use std::{env, fs::File, io, path::Path};
+use std::{env, fs::{self, File}, io, path::Path};
+
+mod encode;
+
+use encode::encode_unicode_data;
 
 const COMPRESSED_DATA_PATH: &str = "unicode_data_encoded.gz";
 const OUT_DATA_PATH: &str = "unicode_data_encoded";
 
+// If this environment variable is set, the data is generated from the `UnicodeData.txt` file in
+// the directory it points to, rather than from the pre-generated compressed data. No other UCD
+// files are read, so the properties from PropList.txt and CompositionExclusions.txt stay fixed at
+// the Unicode 15.0 values in `src/prop_list.rs`.
+const UCD_DIR_VAR: &str = "UTFDUMP_UCD_DIR";
+const UCD_UNICODE_DATA_FILE: &str = "UnicodeData.txt";
+
 fn main() -> io::Result<()> {
     println!("cargo:rerun-if-changed={}", COMPRESSED_DATA_PATH);
+    println!("cargo:rerun-if-env-changed={}", UCD_DIR_VAR);
 
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let out_path = Path::new(&out_dir).join(OUT_DATA_PATH);
-    let out_data_fd = File::create(out_path)?;
-    let mut decoder = flate2::write::GzDecoder::new(out_data_fd);
-    
-    let mut compressed_data_fd = File::open(COMPRESSED_DATA_PATH)?;
 
-    io::copy(&mut compressed_data_fd, &mut decoder)?;
-    decoder.finish()?;
+    match env::var_os(UCD_DIR_VAR) {
+        Some(ucd_dir) => {
+            let unicode_data_path = Path::new(&ucd_dir).join(UCD_UNICODE_DATA_FILE);
+            println!("cargo:rerun-if-changed={}", unicode_data_path.display());
+
+            let input = fs::read_to_string(&unicode_data_path)?;
+            let encoded = encode_unicode_data(&input)
+                .map_err(|err| io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", unicode_data_path.display(), err)
+                ))?;
+
+            fs::write(out_path, encoded)?;
+        },
+
+        None => {
+            let out_data_fd = File::create(out_path)?;
+            let mut decoder = flate2::write::GzDecoder::new(out_data_fd);
+
+            let mut compressed_data_fd = File::open(COMPRESSED_DATA_PATH)?;
+
+            io::copy(&mut compressed_data_fd, &mut decoder)?;
+            decoder.finish()?;
+        },
+    }
 
     Ok(())
 }
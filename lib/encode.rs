@@ -0,0 +1,287 @@
+//! Encodes UnicodeData.txt into the format read by `src/unicode_data.rs`. This is used by the build
+//! script, and by the crate's own tests to check that the format round-trips.
+//!
+//! The encoding must be kept in sync with `data.py`, which documents the encoded format, and with
+//! the decoder in `src/unicode_data.rs`.
+
+use std::collections::HashMap;
+
+const MAGIC_NUMBER: &[u8; 8] = b"UTFDUMP!";
+
+const CATEGORIES: [&str; 30] = [
+    "Lu", "Ll", "Lt", "Mn", "Mc", "Me", "Nd", "Nl", "No", "Zs", "Zl", "Zp", "Cc", "Cf", "Cs", "Co",
+    "Cn", "Lm", "Lo", "Pc", "Pd", "Ps", "Pe", "Pi", "Pf", "Po", "Sm", "Sc", "Sk", "So",
+];
+
+const BIDI_CATEGORIES: [&str; 23] = [
+    "L", "R", "AL", "EN", "ES", "ET", "AN", "CS", "NSM", "BN", "B", "S", "WS", "ON", "LRE", "LRO",
+    "RLE", "RLO", "PDF", "LRI", "RLI", "FSI", "PDI",
+];
+
+// Decomposition kinds 0 and 1 are reserved for "no decomposition" and "canonical decomposition"
+// respectively, so the named kinds start at 2.
+const DECOMP_KINDS: [&str; 16] = [
+    "noBreak", "compat", "super", "fraction", "sub", "font", "circle", "wide", "vertical",
+    "square", "isolated", "final", "initial", "medial", "small", "narrow",
+];
+const DECOMP_KIND_NONE: u16 = 0;
+const DECOMP_KIND_ANON: u16 = 1;
+const DECOMP_KIND_NAMED_OFFSET: u16 = 2;
+
+const GROUP_KIND_NO_VALUE: u8 = 0;
+const GROUP_KIND_USE_PREV_VALUE: u8 = 1;
+
+const NIL_STRING_INDEX: [u8; 3] = [0xff; 3];
+
+struct Group {
+    kind: u8,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Default)]
+struct StringTable {
+    buf: Vec<u8>,
+    indices: HashMap<String, u32>,
+}
+
+impl StringTable {
+    fn push(&mut self, s: &str) -> Result<[u8; 3], String> {
+        if let Some(&index) = self.indices.get(s) {
+            return Ok(encode_string_index(index));
+        }
+
+        let index = u32::try_from(self.buf.len())
+            .ok()
+            .filter(|&index| index < 0xffffff)
+            .ok_or_else(|| "string table is too large".to_owned())?;
+
+        let len = u8::try_from(s.len())
+            .map_err(|_| format!("string is too long for the string table: {}", s))?;
+
+        self.buf.push(len);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.indices.insert(s.to_owned(), index);
+
+        Ok(encode_string_index(index))
+    }
+
+    fn push_optional(&mut self, s: &str) -> Result<[u8; 3], String> {
+        if s.is_empty() {
+            Ok(NIL_STRING_INDEX)
+        } else {
+            self.push(s)
+        }
+    }
+}
+
+fn encode_string_index(index: u32) -> [u8; 3] {
+    let [b0, b1, b2, _] = index.to_le_bytes();
+    [b0, b1, b2]
+}
+
+fn parse_codepoint_string(s: &str) -> Result<String, String> {
+    s.split_whitespace()
+        .map(|codepoint| {
+            u32::from_str_radix(codepoint, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| format!("invalid codepoint: {}", codepoint))
+        })
+        .collect()
+}
+
+fn parse_digit(s: &str) -> Result<u8, String> {
+    if s.is_empty() {
+        return Ok(0xf);
+    }
+
+    s.parse::<u8>()
+        .ok()
+        .filter(|&digit| digit < 0xf)
+        .ok_or_else(|| format!("invalid digit value: {}", s))
+}
+
+pub fn encode_unicode_data(input: &str) -> Result<Vec<u8>, String> {
+    let mut char_table = Vec::<u8>::new();
+    let mut string_table = StringTable::default();
+    let mut groups = Vec::<Group>::new();
+    let mut in_group = false;
+    let mut prev_code = None::<u32>;
+
+    let rows = input
+        .lines()
+        .map(str::trim)
+        .filter(|row| !row.is_empty());
+
+    for row in rows {
+        let cells = row.split(';').map(str::trim).collect::<Vec<_>>();
+
+        let [
+            cell_code,
+            cell_name,
+            cell_category,
+            cell_combining,
+            cell_bidi,
+            cell_decomp,
+            cell_decimal_digit,
+            cell_digit,
+            cell_numeric,
+            cell_mirrored,
+            cell_old_name,
+            cell_comment,
+            cell_uppercase,
+            cell_lowercase,
+            cell_titlecase,
+        ] = <[&str; 15]>::try_from(cells)
+            .map_err(|cells| format!("expected 15 fields, found {}: {}", cells.len(), row))?;
+
+        let code = u32::from_str_radix(cell_code, 16)
+            .map_err(|_| format!("invalid codepoint: {}", cell_code))?;
+
+        if prev_code.is_some_and(|prev_code| prev_code >= code) {
+            return Err(format!("codepoints are not in ascending order: {}", cell_code));
+        }
+
+        // If the previous row was the start of a group, this row should be the end of the group.
+        if in_group {
+            if !(cell_name.starts_with('<') && cell_name.ends_with(", Last>")) {
+                return Err(format!("expected end of range: {}", row));
+            }
+
+            groups.push(Group {
+                kind: GROUP_KIND_USE_PREV_VALUE,
+                start: prev_code.unwrap() + 1,
+                end: code,
+            });
+        }
+
+        // If there is a gap between the previous codepoint (or U+0000, for the first row) and this
+        // codepoint, add a "no value" group to indicate the gap.
+        else {
+            let gap_start = prev_code.map_or(0, |prev_code| prev_code + 1);
+
+            if code > gap_start {
+                groups.push(Group {
+                    kind: GROUP_KIND_NO_VALUE,
+                    start: gap_start,
+                    end: code - 1,
+                });
+            }
+        }
+
+        prev_code = Some(code);
+
+        // The entire group uses the entry created for the start of the group, so there is no
+        // entry to create for the end of the group.
+        if in_group {
+            in_group = false;
+            continue;
+        }
+
+        let name = match cell_name.strip_prefix('<').and_then(|s| s.strip_suffix(", First>")) {
+            Some(group_name) => {
+                in_group = true;
+                string_table.push(group_name)?
+            },
+            None => string_table.push(cell_name)?,
+        };
+
+        let category = CATEGORIES
+            .iter()
+            .position(|&category| category == cell_category)
+            .ok_or_else(|| format!("unknown category: {}", cell_category))?;
+
+        let combining = cell_combining
+            .parse::<u8>()
+            .map_err(|_| format!("invalid combining class: {}", cell_combining))?;
+
+        let bidi = BIDI_CATEGORIES
+            .iter()
+            .position(|&bidi| bidi == cell_bidi)
+            .ok_or_else(|| format!("unknown bidirectional category: {}", cell_bidi))?;
+
+        let (decomp_kind, decomp) = if cell_decomp.is_empty() {
+            (DECOMP_KIND_NONE, NIL_STRING_INDEX)
+        } else {
+            // If the decomposition starts with an angle bracket, the decomposition kind is given
+            // between the angle brackets.
+            let (decomp_kind, decomp_str) = match cell_decomp.strip_prefix('<') {
+                Some(tagged) => {
+                    let (tag, decomp_str) = tagged
+                        .split_once('>')
+                        .ok_or_else(|| format!("invalid decomposition: {}", cell_decomp))?;
+
+                    let decomp_kind = DECOMP_KINDS
+                        .iter()
+                        .position(|kind| kind.eq_ignore_ascii_case(tag.trim()))
+                        .ok_or_else(|| format!("unknown decomposition kind: {}", tag))?;
+
+                    (decomp_kind as u16 + DECOMP_KIND_NAMED_OFFSET, decomp_str)
+                },
+                None => (DECOMP_KIND_ANON, cell_decomp),
+            };
+
+            (decomp_kind, string_table.push(&parse_codepoint_string(decomp_str)?)?)
+        };
+
+        let decimal_digit = parse_digit(cell_decimal_digit)?;
+        let digit = parse_digit(cell_digit)?;
+        let numeric = string_table.push_optional(cell_numeric)?;
+        let mirrored = cell_mirrored == "Y";
+        let old_name = string_table.push_optional(cell_old_name)?;
+        let comment = string_table.push_optional(cell_comment)?;
+        let uppercase = string_table.push_optional(&parse_codepoint_string(cell_uppercase)?)?;
+        let lowercase = string_table.push_optional(&parse_codepoint_string(cell_lowercase)?)?;
+        let titlecase = string_table.push_optional(&parse_codepoint_string(cell_titlecase)?)?;
+
+        // Pack the category, bidirectional category, decomposition kind and mirrored boolean into
+        // two bytes.
+        let flags = (category as u16 & 0x1f)
+            | ((bidi as u16 & 0x1f) << 5)
+            | ((decomp_kind & 0x1f) << 10)
+            | (u16::from(mirrored) << 15);
+
+        char_table.extend_from_slice(&flags.to_le_bytes());
+        char_table.extend_from_slice(&name);
+        char_table.extend_from_slice(&decomp);
+        char_table.extend_from_slice(&numeric);
+        char_table.extend_from_slice(&old_name);
+        char_table.extend_from_slice(&comment);
+        char_table.extend_from_slice(&uppercase);
+        char_table.extend_from_slice(&lowercase);
+        char_table.extend_from_slice(&titlecase);
+        char_table.push(combining);
+        char_table.push((decimal_digit & 0xf) | ((digit & 0xf) << 4));
+    }
+
+    let mut group_table = Vec::<u8>::new();
+    let mut cumulative_len = 0u32;
+
+    for group in &groups {
+        group_table.extend_from_slice(&group.start.to_le_bytes());
+        group_table.extend_from_slice(&group.end.to_le_bytes());
+        // Include the sum of the lengths of all groups before this one.
+        group_table.extend_from_slice(&cumulative_len.to_le_bytes());
+        group_table.push(group.kind);
+
+        cumulative_len += (group.end - group.start) + 1;
+    }
+
+    let table_len = |table: &[u8]| {
+        u32::try_from(table.len())
+            .map(u32::to_le_bytes)
+            .map_err(|_| "table is too large".to_owned())
+    };
+
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(MAGIC_NUMBER);
+    encoded.extend_from_slice(&table_len(&group_table)?);
+    encoded.extend_from_slice(&table_len(&char_table)?);
+    encoded.extend_from_slice(&table_len(&string_table.buf)?);
+    encoded.extend_from_slice(&group_table);
+    encoded.extend_from_slice(&char_table);
+    encoded.extend_from_slice(&string_table.buf);
+
+    Ok(encoded)
+}
@@ -1,3 +1,7 @@
+use core::{fmt, str};
+
+use crate::prop_list;
+
 
 #[derive(Debug)]
 pub struct CharData<'a> {
@@ -38,10 +42,23 @@ impl<'a> CharData<'a> {
         self.category
     }
 
+    /// The character's category as a single-bit mask; see [`Category::mask`].
+    #[inline]
+    #[must_use]
+    pub fn category_mask(&self) -> u32 {
+        self.category.mask()
+    }
+
     pub fn combining_class(&self) -> CombiningClass {
         self.combining
     }
 
+    #[inline]
+    #[must_use]
+    pub fn combining_class_value(&self) -> u8 {
+        self.combining.value()
+    }
+
     #[inline]
     #[must_use]
     pub fn bidi_category(&self) -> BidiCategory {
@@ -72,6 +89,24 @@ impl<'a> CharData<'a> {
         self.numeric
     }
 
+    /// The numeric value of the character as an `f64`, parsed from [`CharData::numeric_value`].
+    /// Fractional values such as "1/3" are divided out, so the result is only approximate, as it
+    /// would also be for an integer too large to be represented exactly by an `f64` (above 2^53).
+    /// This is intended for display and approximate sorting, not exact arithmetic.
+    #[must_use]
+    pub fn numeric_value_f64(&self) -> Option<f64> {
+        let numeric = self.numeric?;
+
+        match numeric.split_once('/') {
+            Some((numerator, denominator)) => {
+                let numerator = numerator.parse::<f64>().ok()?;
+                let denominator = denominator.parse::<f64>().ok()?;
+                Some(numerator / denominator)
+            },
+            None => numeric.parse::<f64>().ok(),
+        }
+    }
+
     #[inline]
     #[must_use]
     pub fn mirrored(&self) -> bool {
@@ -107,45 +142,312 @@ impl<'a> CharData<'a> {
     pub fn titlecase(&self) -> Option<&'a str> {
         self.titlecase
     }
+
+    /// Whether the character has a simple uppercase mapping to something other than itself.
+    #[must_use]
+    pub fn has_uppercase(&self) -> bool {
+        self.maps_to_other(self.uppercase)
+    }
+
+    /// Whether the character has a simple lowercase mapping to something other than itself.
+    #[must_use]
+    pub fn has_lowercase(&self) -> bool {
+        self.maps_to_other(self.lowercase)
+    }
+
+    /// Whether the character has a simple titlecase mapping to something other than itself. As in
+    /// UnicodeData.txt, a missing titlecase mapping means the titlecase mapping is the same as the
+    /// uppercase mapping.
+    #[must_use]
+    pub fn has_titlecase(&self) -> bool {
+        self.maps_to_other(self.titlecase.or(self.uppercase))
+    }
+
+    /// Whether every case mapping present for the character is a single scalar value, so the
+    /// mappings can be applied `char`-to-`char`.
+    #[must_use]
+    pub fn case_mapping_is_simple(&self) -> bool {
+        [self.uppercase, self.lowercase, self.titlecase]
+            .into_iter()
+            .flatten()
+            .all(|mapping| mapping.chars().count() == 1)
+    }
+
+    /// Returns the value of the boolean property with the given name for the character, or `None`
+    /// if the property is not one supported by this crate. Both the long and short property names
+    /// from PropertyAliases.txt are accepted, such as "White_Space" and "WSpace".
+    #[must_use]
+    pub fn bool_property(&self, property: &str) -> Option<bool> {
+        BooleanProperty::find(property).map(|prop| (prop.predicate)(self))
+    }
+
+    /// Returns every boolean property supported by this crate, by its long name, along with its
+    /// value for the character. Use `filter` on the result to list only the properties the
+    /// character has.
+    pub fn boolean_properties(&self) -> impl Iterator<Item = (&'static str, bool)> + '_ {
+        BOOLEAN_PROPERTIES
+            .iter()
+            .map(move |prop| (prop.name, (prop.predicate)(self)))
+    }
+
+    fn maps_to_other(&self, mapping: Option<&str>) -> bool {
+        mapping.is_some_and(|mapping| {
+            let mut chars = mapping.chars();
+            !(chars.next().map(u32::from) == Some(self.codepoint) && chars.next().is_none())
+        })
+    }
+
+    /// Encodes the character as UTF-8 into the given buffer, returning the encoded string. Returns
+    /// `None` if the codepoint is not a Unicode scalar value (i.e. it is a surrogate), or if the
+    /// buffer is too small.
+    pub fn encode_utf8<'b>(&self, buf: &'b mut [u8]) -> Option<&'b str> {
+        let c = char::from_u32(self.codepoint)?;
+
+        if buf.len() < c.len_utf8() {
+            return None;
+        }
+
+        Some(c.encode_utf8(buf))
+    }
+
+    /// Encodes the character as UTF-16 into the given buffer, returning the slice of the buffer
+    /// containing the encoded character. Returns `None` if the codepoint is not a Unicode scalar
+    /// value (i.e. it is a surrogate), or if the buffer is too small.
+    pub fn encode_utf16<'b>(&self, buf: &'b mut [u16]) -> Option<&'b [u16]> {
+        let c = char::from_u32(self.codepoint)?;
+
+        if buf.len() < c.len_utf16() {
+            return None;
+        }
+
+        Some(c.encode_utf16(buf))
+    }
+
+    /// Whether the character is a mark; that is, its category is `Mn`, `Mc` or `Me`. This is not the
+    /// same as having a non-zero combining class: many marks, such as most spacing marks, have a
+    /// combining class of 0. See [`Category::is_mark`].
+    #[inline]
+    #[must_use]
+    pub fn is_mark(&self) -> bool {
+        self.category.is_mark()
+    }
+
+    /// Whether the character has the `White_Space` property from PropList.txt.
+    #[must_use]
+    pub fn is_whitespace(&self) -> bool {
+        prop_list::table_contains(prop_list::WHITE_SPACE, self.codepoint)
+    }
+
+    /// Whether the character has the `Dash` property from PropList.txt.
+    #[must_use]
+    pub fn is_dash(&self) -> bool {
+        prop_list::table_contains(prop_list::DASH, self.codepoint)
+    }
+
+    /// Whether the character has the `Hyphen` property from PropList.txt.
+    #[must_use]
+    pub fn is_hyphen(&self) -> bool {
+        prop_list::table_contains(prop_list::HYPHEN, self.codepoint)
+    }
+
+    /// Whether the character has the `Quotation_Mark` property from PropList.txt.
+    #[must_use]
+    pub fn is_quotation_mark(&self) -> bool {
+        prop_list::table_contains(prop_list::QUOTATION_MARK, self.codepoint)
+    }
+
+    /// Whether the character has the `Terminal_Punctuation` property from PropList.txt.
+    #[must_use]
+    pub fn is_terminal_punctuation(&self) -> bool {
+        prop_list::table_contains(prop_list::TERMINAL_PUNCTUATION, self.codepoint)
+    }
+
+    /// Whether the character has the `Ideographic` property from PropList.txt. This includes
+    /// compatibility ideographs and ideographs from scripts other than Han, such as Tangut.
+    #[must_use]
+    pub fn is_ideographic(&self) -> bool {
+        prop_list::table_contains(prop_list::IDEOGRAPHIC, self.codepoint)
+    }
+
+    /// Whether the character has the `Unified_Ideograph` property from PropList.txt.
+    #[must_use]
+    pub fn is_unified_ideograph(&self) -> bool {
+        prop_list::table_contains(prop_list::UNIFIED_IDEOGRAPH, self.codepoint)
+    }
 }
 
+pub(crate) struct BooleanProperty {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    predicate: fn(&CharData) -> bool,
+}
+
+impl BooleanProperty {
+    /// Finds the supported boolean property with the given long or short name.
+    pub(crate) fn find(property: &str) -> Option<&'static Self> {
+        BOOLEAN_PROPERTIES
+            .iter()
+            .find(|prop| prop.name == property || prop.aliases.contains(&property))
+    }
+}
+
+static BOOLEAN_PROPERTIES: [BooleanProperty; 8] = [
+    BooleanProperty {
+        name: "Bidi_Mirrored",
+        aliases: &["Bidi_M"],
+        predicate: |c| c.mirrored(),
+    },
+    BooleanProperty {
+        name: "White_Space",
+        aliases: &["WSpace", "space"],
+        predicate: |c| c.is_whitespace(),
+    },
+    BooleanProperty {
+        name: "Dash",
+        aliases: &[],
+        predicate: |c| c.is_dash(),
+    },
+    BooleanProperty {
+        name: "Hyphen",
+        aliases: &[],
+        predicate: |c| c.is_hyphen(),
+    },
+    BooleanProperty {
+        name: "Quotation_Mark",
+        aliases: &["QMark"],
+        predicate: |c| c.is_quotation_mark(),
+    },
+    BooleanProperty {
+        name: "Terminal_Punctuation",
+        aliases: &["Term"],
+        predicate: |c| c.is_terminal_punctuation(),
+    },
+    BooleanProperty {
+        name: "Ideographic",
+        aliases: &["Ideo"],
+        predicate: |c| c.is_ideographic(),
+    },
+    BooleanProperty {
+        name: "Unified_Ideograph",
+        aliases: &["UIdeo"],
+        predicate: |c| c.is_unified_ideograph(),
+    },
+];
+
+const NAMED_COMBINING_CLASSES: [(u8, &str); 21] = [
+    (0, "Not_Reordered"),
+    (1, "Overlay"),
+    (6, "Han_Reading"),
+    (7, "Nukta"),
+    (8, "Kana_Voicing"),
+    (9, "Virama"),
+    (200, "Attached_Below_Left"),
+    (202, "Attached_Below"),
+    (214, "Attached_Above"),
+    (216, "Attached_Above_Right"),
+    (218, "Below_Left"),
+    (220, "Below"),
+    (222, "Below_Right"),
+    (224, "Left"),
+    (226, "Right"),
+    (228, "Above_Left"),
+    (230, "Above"),
+    (232, "Above_Right"),
+    (233, "Double_Below"),
+    (234, "Double_Above"),
+    (240, "Iota_Subscript"),
+];
+
+/// A canonical combining class. Combining classes are ordered by their numeric value, which is the
+/// order that canonical ordering sorts combining marks into.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct CombiningClass(pub u8);
 
 impl CombiningClass {
+    #[inline]
+    #[must_use]
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
     pub fn name(self) -> Option<&'static str> {
+        NAMED_COMBINING_CLASSES
+            .iter()
+            .find(|&&(value, _)| value == self.0)
+            .map(|&(_, name)| name)
+    }
+
+    /// Returns the combining class with the given name, for example `"Above"`. This is the inverse
+    /// of [`CombiningClass::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        NAMED_COMBINING_CLASSES
+            .iter()
+            .find(|&&(_, class_name)| class_name == name)
+            .map(|&(value, _)| Self(value))
+    }
+
+    /// Returns an iterator over every combining class which has a name, in ascending order.
+    pub fn named_classes() -> impl Iterator<Item = Self> {
+        NAMED_COMBINING_CLASSES
+            .iter()
+            .map(|&(value, _)| Self(value))
+    }
+
+    /// Whether the combining class is non-zero, meaning that the character takes part in canonical
+    /// ordering. Not every mark has a non-zero combining class; to check whether a character is a
+    /// mark, use [`CharData::is_mark`] instead.
+    pub fn is_combining(self) -> bool {
+        self.0 != 0
+    }
+
+    /// The position where marks with this combining class are placed relative to their base
+    /// character, or `None` if the class is not one of the positional classes 200 to 234. Attached
+    /// classes share a position with their unattached equivalents; for example, both
+    /// `Attached_Above` (214) and `Above` (230) are [`MarkPosition::Above`].
+    #[must_use]
+    pub fn position(self) -> Option<MarkPosition> {
         match self.0 {
-            0 => Some("Not_Reordered"),
-            1 => Some("Overlay"),
-            6 => Some("Han_Reading"),
-            7 => Some("Nukta"),
-            8 => Some("Kana_Voicing"),
-            9 => Some("Virama"),
-            200 => Some("Attached_Below_Left"),
-            202 => Some("Attached_Below"),
-            214 => Some("Attached_Above"),
-            216 => Some("Attached_Above_Right"),
-            218 => Some("Below_Left"),
-            220 => Some("Below"),
-            222 => Some("Below_Right"),
-            224 => Some("Left"),
-            226 => Some("Right"),
-            228 => Some("Above_Left"),
-            230 => Some("Above"),
-            232 => Some("Above_Right"),
-            233 => Some("Double_Below"),
-            234 => Some("Double_Above"),
-            240 => Some("Iota_Subscript"),
+            200 | 218 => Some(MarkPosition::BelowLeft),
+            202 | 220 => Some(MarkPosition::Below),
+            204 | 222 => Some(MarkPosition::BelowRight),
+            208 | 224 => Some(MarkPosition::Left),
+            210 | 226 => Some(MarkPosition::Right),
+            212 | 228 => Some(MarkPosition::AboveLeft),
+            214 | 230 => Some(MarkPosition::Above),
+            216 | 232 => Some(MarkPosition::AboveRight),
+            233 => Some(MarkPosition::DoubleBelow),
+            234 => Some(MarkPosition::DoubleAbove),
             _ => None,
         }
     }
 
-    pub fn is_combining(self) -> bool {
-        self.0 != 0
+    /// Whether the combining class is one of the attached classes 200 to 216, for marks which are
+    /// attached to their base character rather than placed next to it.
+    #[inline]
+    #[must_use]
+    pub fn is_attached(self) -> bool {
+        (200..=216).contains(&self.0)
     }
 }
 
+/// The position of a mark relative to its base character; see [`CombiningClass::position`].
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MarkPosition {
+    BelowLeft,
+    Below,
+    BelowRight,
+    Left,
+    Right,
+    AboveLeft,
+    Above,
+    AboveRight,
+    /// Spanning the base character and the character after it, below them.
+    DoubleBelow,
+    /// Spanning the base character and the character after it, above them.
+    DoubleAbove,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum Category {
     Lu,
     Ll,
@@ -216,6 +518,170 @@ impl Category {
         }
     }
 
+    /// Returns the category with the given numeric code, as returned by [`Category::to_byte`].
+    #[inline]
+    #[must_use]
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        Self::decode(byte)
+    }
+
+    /// The numeric code for the category, from 0 to 29. These are the codes used by the encoded
+    /// data format, so they are stable; changing them would mean changing the format itself.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::Lu => 0,
+            Self::Ll => 1,
+            Self::Lt => 2,
+            Self::Mn => 3,
+            Self::Mc => 4,
+            Self::Me => 5,
+            Self::Nd => 6,
+            Self::Nl => 7,
+            Self::No => 8,
+            Self::Zs => 9,
+            Self::Zl => 10,
+            Self::Zp => 11,
+            Self::Cc => 12,
+            Self::Cf => 13,
+            Self::Cs => 14,
+            Self::Co => 15,
+            Self::Cn => 16,
+            Self::Lm => 17,
+            Self::Lo => 18,
+            Self::Pc => 19,
+            Self::Pd => 20,
+            Self::Ps => 21,
+            Self::Pe => 22,
+            Self::Pi => 23,
+            Self::Pf => 24,
+            Self::Po => 25,
+            Self::Sm => 26,
+            Self::Sc => 27,
+            Self::Sk => 28,
+            Self::So => 29,
+        }
+    }
+
+    /// A mask with only the bit for this category set, at the position given by
+    /// [`Category::to_byte`]. Note that this is not the same bit order as ICU's category masks.
+    #[inline]
+    #[must_use]
+    pub fn mask(self) -> u32 {
+        1 << self.to_byte()
+    }
+
+    /// Returns an iterator over every category, in the order they are encoded.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..=u8::MAX).map_while(Self::decode)
+    }
+
+    /// Parses a two-letter category abbreviation such as "Lu". The abbreviation is case-sensitive.
+    #[must_use]
+    pub fn from_abbreviation(abbr: &str) -> Option<Self> {
+        Self::all().find(|category| category.abbreviation() == abbr)
+    }
+
+    /// Parses a full category name such as "Letter, Uppercase", ignoring ASCII case.
+    #[must_use]
+    pub fn from_full_name(name: &str) -> Option<Self> {
+        Self::all().find(|category| category.full_name().eq_ignore_ascii_case(name))
+    }
+
+    /// The major category that this category belongs to, given by the first letter of its
+    /// abbreviation.
+    #[must_use]
+    pub fn major_category(self) -> MajorCategory {
+        match self {
+            Self::Lu | Self::Ll | Self::Lt | Self::Lm | Self::Lo => MajorCategory::Letter,
+            Self::Mn | Self::Mc | Self::Me => MajorCategory::Mark,
+            Self::Nd | Self::Nl | Self::No => MajorCategory::Number,
+            Self::Pc | Self::Pd | Self::Ps | Self::Pe | Self::Pi | Self::Pf | Self::Po => {
+                MajorCategory::Punctuation
+            },
+            Self::Sm | Self::Sc | Self::Sk | Self::So => MajorCategory::Symbol,
+            Self::Zs | Self::Zl | Self::Zp => MajorCategory::Separator,
+            Self::Cc | Self::Cf | Self::Cs | Self::Co | Self::Cn => MajorCategory::Other,
+        }
+    }
+
+    /// Whether the category is `Mn` (Mark, Nonspacing), `Mc` (Mark, Spacing Combining) or `Me`
+    /// (Mark, Enclosing).
+    #[inline]
+    #[must_use]
+    pub fn is_mark(self) -> bool {
+        matches!(self, Self::Mn | Self::Mc | Self::Me)
+    }
+
+    /// Whether the category is `Nd`, `Nl` or `No`. This matches the definition used by
+    /// [`char::is_numeric`], but using the Unicode version of this crate's data.
+    #[inline]
+    #[must_use]
+    pub fn is_std_numeric(self) -> bool {
+        matches!(self, Self::Nd | Self::Nl | Self::No)
+    }
+
+    /// Whether the category is `Cc`. This matches the definition used by [`char::is_control`], but
+    /// using the Unicode version of this crate's data.
+    ///
+    /// Not every `char` predicate can be reproduced from the category alone. For example,
+    /// [`char::is_alphabetic`] (and so [`char::is_alphanumeric`]) uses the derived `Alphabetic`
+    /// property, which includes many marks such as U+0345 COMBINING GREEK YPOGEGRAMMENI, and
+    /// [`char::is_lowercase`] uses the derived `Lowercase` property, which includes some `Lm` and
+    /// `So` characters.
+    #[inline]
+    #[must_use]
+    pub fn is_std_control(self) -> bool {
+        matches!(self, Self::Cc)
+    }
+
+    /// Whether the category is `Ps` (Punctuation, Open), which includes opening brackets.
+    #[inline]
+    #[must_use]
+    pub fn is_open_punctuation(self) -> bool {
+        matches!(self, Self::Ps)
+    }
+
+    /// Whether the category is `Pe` (Punctuation, Close), which includes closing brackets.
+    #[inline]
+    #[must_use]
+    pub fn is_close_punctuation(self) -> bool {
+        matches!(self, Self::Pe)
+    }
+
+    /// Whether the category is `Pi` (Punctuation, Initial Quote) or `Pf` (Punctuation, Final Quote).
+    /// Note that some quotation marks, such as U+0022 QUOTATION MARK, are categorised as `Po` and
+    /// so are not included.
+    #[inline]
+    #[must_use]
+    pub fn is_quote(self) -> bool {
+        matches!(self, Self::Pi | Self::Pf)
+    }
+
+    /// A suggested RGB colour for displaying characters of this category, chosen according to the
+    /// category's major class (letter, mark, number, punctuation, symbol, separator or other) so
+    /// that renderers can share a consistent palette.
+    #[must_use]
+    pub fn display_color(self) -> (u8, u8, u8) {
+        match self {
+            // Letters
+            Self::Lu | Self::Ll | Self::Lt | Self::Lm | Self::Lo => (0x4c, 0xaf, 0x50),
+            // Marks
+            Self::Mn | Self::Mc | Self::Me => (0xab, 0x47, 0xbc),
+            // Numbers
+            Self::Nd | Self::Nl | Self::No => (0x29, 0x79, 0xff),
+            // Punctuation
+            Self::Pc | Self::Pd | Self::Ps | Self::Pe | Self::Pi | Self::Pf | Self::Po => {
+                (0x9e, 0x9e, 0x9e)
+            },
+            // Symbols
+            Self::Sm | Self::Sc | Self::Sk | Self::So => (0xff, 0x98, 0x00),
+            // Separators
+            Self::Zs | Self::Zl | Self::Zp => (0x00, 0xbc, 0xd4),
+            // Other
+            Self::Cc | Self::Cf | Self::Cs | Self::Co | Self::Cn => (0xf4, 0x43, 0x36),
+        }
+    }
+
     pub fn abbreviation(self) -> &'static str {
         match self {
             Self::Lu => "Lu",
@@ -287,6 +753,163 @@ impl Category {
     }
 }
 
+/// Parses a category from either its abbreviation or its full name. The abbreviation is tried
+/// first, so "Lu" and "Letter, Uppercase" both parse to [`Category::Lu`].
+///
+/// Single-letter major categories such as "L" are not accepted, as they stand for more than one
+/// category. A filter accepting any of the three forms should try this first and then fall back
+/// to [`MajorCategory::from_letter`], giving the precedence: exact abbreviation, then full name,
+/// then major category letter.
+impl str::FromStr for Category {
+    type Err = ParseCategoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_abbreviation(s)
+            .or_else(|| Self::from_full_name(s))
+            .ok_or(ParseCategoryError)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseCategoryError;
+
+impl fmt::Display for ParseCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown general category")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCategoryError {}
+
+/// A group of general categories sharing the same first letter, such as `L` for letters.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum MajorCategory {
+    Letter,
+    Mark,
+    Number,
+    Punctuation,
+    Symbol,
+    Separator,
+    Other,
+}
+
+impl MajorCategory {
+    /// Parses the letter used for the major category, such as 'L' for letters. The letter is
+    /// case-sensitive.
+    #[must_use]
+    pub fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'L' => Some(Self::Letter),
+            'M' => Some(Self::Mark),
+            'N' => Some(Self::Number),
+            'P' => Some(Self::Punctuation),
+            'S' => Some(Self::Symbol),
+            'Z' => Some(Self::Separator),
+            'C' => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            Self::Letter => 'L',
+            Self::Mark => 'M',
+            Self::Number => 'N',
+            Self::Punctuation => 'P',
+            Self::Symbol => 'S',
+            Self::Separator => 'Z',
+            Self::Other => 'C',
+        }
+    }
+
+    /// Returns an iterator over the categories belonging to this major category.
+    pub fn categories(self) -> impl Iterator<Item = Category> {
+        Category::all().filter(move |category| category.major_category() == self)
+    }
+}
+
+/// A set of categories, stored as a bitmask with one bit per category as given by
+/// [`Category::mask`]. Checking whether a category is in the set is a single bitwise AND.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct CategorySet(u32);
+
+impl CategorySet {
+    const ALL_MASK: u32 = (1 << 30) - 1;
+
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Builds a set from a bitmask. Bits which do not correspond to any category are ignored.
+    #[inline]
+    #[must_use]
+    pub const fn from_mask(mask: u32) -> Self {
+        Self(mask & Self::ALL_MASK)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn to_mask(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains(self, category: Category) -> bool {
+        self.0 & category.mask() != 0
+    }
+
+    #[inline]
+    pub fn insert(&mut self, category: Category) {
+        self.0 |= category.mask();
+    }
+
+    #[inline]
+    pub fn remove(&mut self, category: Category) {
+        self.0 &= !category.mask();
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns an iterator over the categories in the set, in the order they are encoded.
+    pub fn iter(self) -> impl Iterator<Item = Category> {
+        Category::all().filter(move |&category| self.contains(category))
+    }
+}
+
+impl FromIterator<Category> for CategorySet {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Category>,
+    {
+        let mut set = Self::new();
+        for category in iter {
+            set.insert(category);
+        }
+        set
+    }
+}
+
+impl From<MajorCategory> for CategorySet {
+    fn from(major: MajorCategory) -> Self {
+        major.categories().collect()
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum BidiCategory {
     L,
@@ -344,6 +967,85 @@ impl BidiCategory {
         }
     }
 
+    /// Returns the bidirectional category with the given numeric code, as returned by
+    /// [`BidiCategory::to_byte`].
+    #[inline]
+    #[must_use]
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        Self::decode(byte)
+    }
+
+    /// Returns an iterator over every bidirectional category, in the order they are encoded.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..=u8::MAX).map_while(Self::decode)
+    }
+
+    /// The numeric code for the bidirectional category, from 0 to 22. Like
+    /// [`Category::to_byte`], these are the codes used by the encoded data format.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::L => 0,
+            Self::R => 1,
+            Self::Al => 2,
+            Self::En => 3,
+            Self::Es => 4,
+            Self::Et => 5,
+            Self::An => 6,
+            Self::Cs => 7,
+            Self::Nsm => 8,
+            Self::Bn => 9,
+            Self::B => 10,
+            Self::S => 11,
+            Self::Ws => 12,
+            Self::On => 13,
+            Self::Lre => 14,
+            Self::Lro => 15,
+            Self::Rle => 16,
+            Self::Rlo => 17,
+            Self::Pdf => 18,
+            Self::Lri => 19,
+            Self::Rli => 20,
+            Self::Fsi => 21,
+            Self::Pdi => 22,
+        }
+    }
+
+    /// The strong direction of the category, if any. Only `L` is left-to-right and only `R` and
+    /// `AL` are right-to-left; every other category, including the number categories `EN` and
+    /// `AN`, is treated as neutral. This is a simplification for when the full bidirectional
+    /// algorithm is not needed.
+    #[must_use]
+    pub fn base_direction(self) -> Direction {
+        match self {
+            Self::L => Direction::Ltr,
+            Self::R | Self::Al => Direction::Rtl,
+            _ => Direction::Neutral,
+        }
+    }
+
+    /// Whether the category is one of the explicit embedding and override formatting codes used
+    /// by UAX #9: `LRE`, `RLE`, `LRO`, `RLO` or `PDF`. The isolate formatting codes are not
+    /// included; see [`BidiCategory::is_isolate`].
+    #[inline]
+    #[must_use]
+    pub fn is_explicit_formatting(self) -> bool {
+        matches!(self, Self::Lre | Self::Rle | Self::Lro | Self::Rlo | Self::Pdf)
+    }
+
+    /// Whether the category is `LRI`, `RLI` or `FSI`, which begin an isolate.
+    #[inline]
+    #[must_use]
+    pub fn is_isolate_initiator(self) -> bool {
+        matches!(self, Self::Lri | Self::Rli | Self::Fsi)
+    }
+
+    /// Whether the category is an isolate initiator or `PDI`, which ends an isolate.
+    #[inline]
+    #[must_use]
+    pub fn is_isolate(self) -> bool {
+        self.is_isolate_initiator() || matches!(self, Self::Pdi)
+    }
+
     pub fn abbreviation(self) -> &'static str {
         match self {
             Self::L => "L",
@@ -401,6 +1103,14 @@ impl BidiCategory {
     }
 }
 
+/// A simplified text direction; see [`BidiCategory::base_direction`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+    Neutral,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct DecompMapping<'a> {
     kind: Option<DecompKind>,
@@ -423,6 +1133,53 @@ impl<'a> DecompMapping<'a> {
     pub fn value(self) -> &'a str {
         self.value
     }
+
+    /// Returns a value which displays the mapping as it is written in UnicodeData.txt, with the
+    /// tag in angle brackets followed by the space-separated hexadecimal codepoints, for example
+    /// `<compat> 0066 0066`.
+    #[inline]
+    #[must_use]
+    pub fn ucd_form(self) -> UcdDecompMapping<'a> {
+        UcdDecompMapping(self)
+    }
+
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_ucd_string(self) -> String {
+        self.ucd_form().to_string()
+    }
+}
+
+/// Displays the mapping value, prefixed with its tag in angle brackets if it is a compatibility
+/// mapping, for example `<compat> ff`.
+impl<'a> fmt::Display for DecompMapping<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(kind) = self.kind {
+            write!(f, "<{}> ", kind.name())?;
+        }
+        f.write_str(self.value)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UcdDecompMapping<'a>(DecompMapping<'a>);
+
+impl<'a> fmt::Display for UcdDecompMapping<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(kind) = self.0.kind {
+            write!(f, "<{}> ", kind.name())?;
+        }
+
+        let mut chars = self.0.value.chars();
+        if let Some(c) = chars.next() {
+            write!(f, "{:04X}", u32::from(c))?;
+            for c in chars {
+                write!(f, " {:04X}", u32::from(c))?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -499,3 +1256,371 @@ impl OptionalDecompKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::UnicodeData;
+
+    use super::{
+        BidiCategory,
+        Category,
+        CategorySet,
+        CombiningClass,
+        Direction,
+        MajorCategory,
+        MarkPosition,
+        ParseCategoryError,
+    };
+
+    #[test]
+    fn test_category_byte_round_trip() {
+        for byte in 0..=u8::MAX {
+            if let Some(category) = Category::decode(byte) {
+                assert_eq!(category.to_byte(), byte);
+            }
+        }
+    }
+
+    #[test]
+    fn test_combining_class_names() {
+        assert_eq!(CombiningClass(230).name(), Some("Above"));
+        assert_eq!(CombiningClass(231).name(), None);
+        assert_eq!(CombiningClass::from_name("Above"), Some(CombiningClass(230)));
+        assert_eq!(CombiningClass::from_name("above"), None);
+
+        assert_eq!(CombiningClass::named_classes().count(), 21);
+
+        for ccc in CombiningClass::named_classes() {
+            assert_eq!(CombiningClass::from_name(ccc.name().unwrap()), Some(ccc));
+        }
+
+        for value in 0..=u8::MAX {
+            let ccc = CombiningClass(value);
+            assert_eq!(ccc.name().is_some(), CombiningClass::named_classes().any(|c| c == ccc));
+        }
+    }
+
+    #[test]
+    fn test_combining_class_position() {
+        assert_eq!(CombiningClass(230).position(), Some(MarkPosition::Above));
+        assert!(!CombiningClass(230).is_attached());
+        assert_eq!(CombiningClass(202).position(), Some(MarkPosition::Below));
+        assert!(CombiningClass(202).is_attached());
+        assert_eq!(CombiningClass(214).position(), CombiningClass(230).position());
+        assert_eq!(CombiningClass(234).position(), Some(MarkPosition::DoubleAbove));
+
+        for value in [0, 1, 9, 84, 240] {
+            assert_eq!(CombiningClass(value).position(), None);
+            assert!(!CombiningClass(value).is_attached());
+        }
+
+        // Every named positional class has a position.
+        let positional = CombiningClass::named_classes()
+            .filter(|ccc| (200..=234).contains(&ccc.value()));
+
+        for ccc in positional {
+            assert!(ccc.position().is_some());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_decomp_mapping_display() {
+        use super::{DecompKind, DecompMapping};
+
+        let compat = DecompMapping::new(Some(DecompKind::Compat), "ff");
+        assert_eq!(compat.to_string(), "<compat> ff");
+        assert_eq!(compat.to_ucd_string(), "<compat> 0066 0066");
+
+        let canonical = DecompMapping::new(None, "A\u{30a}");
+        assert_eq!(canonical.to_string(), "A\u{30a}");
+        assert_eq!(canonical.to_ucd_string(), "0041 030A");
+
+        let astral = DecompMapping::new(None, "\u{1d157}\u{1d165}");
+        assert_eq!(astral.to_ucd_string(), "1D157 1D165");
+    }
+
+    #[test]
+    fn test_char_data_encode() {
+        let data = UnicodeData::new().unwrap();
+
+        let mut buf = [0u8; 4];
+        assert_eq!(data.get(0xe9).unwrap().encode_utf8(&mut buf), Some("\u{e9}"));
+        assert_eq!(data.get(0x1f600).unwrap().encode_utf8(&mut buf), Some("\u{1f600}"));
+        assert_eq!(data.get(0x1f600).unwrap().encode_utf8(&mut buf[..3]), None);
+        assert_eq!(data.get(0xd800).unwrap().encode_utf8(&mut buf), None);
+
+        let mut buf = [0u16; 2];
+        assert_eq!(data.get(0xe9).unwrap().encode_utf16(&mut buf), Some(&[0xe9][..]));
+        assert_eq!(
+            data.get(0x1f600).unwrap().encode_utf16(&mut buf),
+            Some(&[0xd83d, 0xde00][..])
+        );
+        assert_eq!(data.get(0x1f600).unwrap().encode_utf16(&mut buf[..1]), None);
+        assert_eq!(data.get(0xd800).unwrap().encode_utf16(&mut buf), None);
+    }
+
+    #[test]
+    fn test_category_punctuation_predicates() {
+        assert!(Category::Ps.is_open_punctuation());
+        assert!(!Category::Pe.is_open_punctuation());
+        assert!(Category::Pe.is_close_punctuation());
+        assert!(!Category::Ps.is_close_punctuation());
+        assert!(Category::Pi.is_quote());
+        assert!(Category::Pf.is_quote());
+        assert!(!Category::Po.is_quote());
+    }
+
+    #[test]
+    fn test_digit_values() {
+        let data = UnicodeData::new().unwrap();
+
+        let five = data.get(0x35).unwrap();
+        assert_eq!(five.decimal_digit_value(), Some(5));
+        assert_eq!(five.digit_value(), Some(5));
+
+        // Superscript two and circled digit one are digits but not decimal digits.
+        assert_eq!(data.get(0xb2).unwrap().decimal_digit_value(), None);
+        assert_eq!(data.get(0xb2).unwrap().digit_value(), Some(2));
+        assert_eq!(data.get(0x2460).unwrap().digit_value(), Some(1));
+
+        assert_eq!(data.get(0xbd).unwrap().digit_value(), None);
+        assert_eq!(data.get(0x41).unwrap().decimal_digit_value(), None);
+        assert_eq!(data.get(0x41).unwrap().digit_value(), None);
+    }
+
+    #[test]
+    fn test_numeric_value_f64() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.get(0x35).unwrap().numeric_value_f64(), Some(5.0));
+        assert_eq!(data.get(0xbd).unwrap().numeric_value_f64(), Some(0.5));
+        assert_eq!(data.get(0xf33).unwrap().numeric_value_f64(), Some(-0.5));
+        assert_eq!(data.get(0x216b).unwrap().numeric_value_f64(), Some(12.0));
+        assert_eq!(data.get(0x16b61).unwrap().numeric_value_f64(), Some(1e12));
+        assert_eq!(data.get(0x61).unwrap().numeric_value_f64(), None);
+
+        let third = data.get(0x2153).unwrap().numeric_value_f64().unwrap();
+        assert!((third - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_std_equivalents() {
+        let data = UnicodeData::new().unwrap();
+
+        // Characters whose category changed after the Unicode version of this crate's data may
+        // disagree with the standard library, so only check a sample from stable blocks.
+        let samples = ('\0'..='\u{24ff}')
+            .chain('\u{3000}'..='\u{33ff}')
+            .chain('\u{ff00}'..='\u{ffef}')
+            .chain('\u{10100}'..='\u{1018f}');
+
+        for c in samples {
+            if let Some(char_data) = data.get(u32::from(c)) {
+                assert_eq!(char_data.category().is_std_numeric(), c.is_numeric(), "{:?}", c);
+                assert_eq!(char_data.category().is_std_control(), c.is_control(), "{:?}", c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_mark() {
+        let data = UnicodeData::new().unwrap();
+
+        // U+0301 COMBINING ACUTE ACCENT is a mark with a non-zero combining class.
+        let acute = data.get(0x301).unwrap();
+        assert!(acute.is_mark());
+        assert!(acute.combining_class().is_combining());
+
+        // U+0903 DEVANAGARI SIGN VISARGA and U+20DD COMBINING ENCLOSING CIRCLE are marks with a
+        // combining class of 0.
+        for codepoint in [0x903, 0x20dd] {
+            let c = data.get(codepoint).unwrap();
+            assert!(c.is_mark());
+            assert!(!c.combining_class().is_combining());
+        }
+
+        assert!(!data.get(0x61).unwrap().is_mark());
+        assert!(Category::Me.is_mark());
+        assert!(!Category::Lm.is_mark());
+    }
+
+    #[test]
+    fn test_case_mappings() {
+        let data = UnicodeData::new().unwrap();
+
+        let a = data.get(0x61).unwrap();
+        assert!(a.has_uppercase());
+        assert!(!a.has_lowercase());
+        assert!(a.has_titlecase());
+        assert!(a.case_mapping_is_simple());
+
+        // U+01C5 LATIN CAPITAL LETTER D WITH SMALL LETTER Z WITH CARON is its own titlecase.
+        let dz = data.get(0x1c5).unwrap();
+        assert!(dz.has_uppercase());
+        assert!(dz.has_lowercase());
+        assert!(!dz.has_titlecase());
+        assert!(dz.case_mapping_is_simple());
+
+        let digit = data.get(0x31).unwrap();
+        assert!(!digit.has_uppercase());
+        assert!(!digit.has_lowercase());
+        assert!(!digit.has_titlecase());
+        assert!(digit.case_mapping_is_simple());
+    }
+
+    #[test]
+    fn test_boolean_properties() {
+        let data = UnicodeData::new().unwrap();
+
+        // U+2010 HYPHEN
+        let hyphen = data.get(0x2010).unwrap();
+        assert!(hyphen
+            .boolean_properties()
+            .filter(|&(_, value)| value)
+            .map(|(name, _)| name)
+            .eq(["Dash", "Hyphen"]));
+
+        for (name, value) in hyphen.boolean_properties() {
+            assert_eq!(hyphen.bool_property(name), Some(value));
+        }
+
+        assert_eq!(hyphen.bool_property("Dash"), Some(true));
+        assert_eq!(hyphen.bool_property("WSpace"), Some(false));
+        assert_eq!(hyphen.bool_property("Alphabetic"), None);
+
+        let a = data.get(0x61).unwrap();
+        assert_eq!(a.boolean_properties().count(), 8);
+        assert!(a.boolean_properties().all(|(_, value)| !value));
+    }
+
+    #[test]
+    fn test_parse_category() {
+        assert_eq!(Category::all().count(), 30);
+
+        for category in Category::all() {
+            assert_eq!(category.abbreviation().parse(), Ok(category));
+            assert_eq!(category.full_name().parse(), Ok(category));
+            assert_eq!(
+                category.abbreviation().chars().next(),
+                Some(category.major_category().letter())
+            );
+        }
+
+        assert_eq!("letter, uppercase".parse(), Ok(Category::Lu));
+        assert_eq!("lu".parse::<Category>(), Err(ParseCategoryError));
+        assert_eq!("L".parse::<Category>(), Err(ParseCategoryError));
+        assert_eq!("".parse::<Category>(), Err(ParseCategoryError));
+
+        assert_eq!(MajorCategory::from_letter('L'), Some(MajorCategory::Letter));
+        assert_eq!(MajorCategory::from_letter('l'), None);
+        assert!(MajorCategory::Letter.categories().eq([
+            Category::Lu,
+            Category::Ll,
+            Category::Lt,
+            Category::Lm,
+            Category::Lo,
+        ]));
+        assert_eq!(MajorCategory::Separator.categories().count(), 3);
+    }
+
+    #[test]
+    fn test_bidi_category_byte_round_trip() {
+        for byte in 0..=u8::MAX {
+            if let Some(bidi) = BidiCategory::decode(byte) {
+                assert_eq!(bidi.to_byte(), byte);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stable_byte_codes() {
+        // These must match the order of the tables in `encode.rs` and `data.py`.
+        assert!(Category::all().map(Category::abbreviation).eq([
+            "Lu", "Ll", "Lt", "Mn", "Mc", "Me", "Nd", "Nl", "No", "Zs", "Zl", "Zp", "Cc", "Cf",
+            "Cs", "Co", "Cn", "Lm", "Lo", "Pc", "Pd", "Ps", "Pe", "Pi", "Pf", "Po", "Sm", "Sc",
+            "Sk", "So",
+        ]));
+
+        assert!(BidiCategory::all().map(BidiCategory::abbreviation).eq([
+            "L", "R", "AL", "EN", "ES", "ET", "AN", "CS", "NSM", "BN", "B", "S", "WS", "ON", "LRE",
+            "LRO", "RLE", "RLO", "PDF", "LRI", "RLI", "FSI", "PDI",
+        ]));
+
+        assert_eq!(Category::from_byte(Category::So.to_byte()), Some(Category::So));
+        assert_eq!(Category::from_byte(30), None);
+        assert_eq!(BidiCategory::from_byte(BidiCategory::Pdi.to_byte()), Some(BidiCategory::Pdi));
+        assert_eq!(BidiCategory::from_byte(23), None);
+    }
+
+    #[test]
+    fn test_category_masks() {
+        let all = Category::all().fold(0u32, |mask, category| {
+            assert_eq!(category.mask().count_ones(), 1);
+            assert_eq!(mask & category.mask(), 0);
+            mask | category.mask()
+        });
+
+        assert_eq!(all, (1 << 30) - 1);
+        assert_eq!(CategorySet::from_mask(u32::MAX).to_mask(), all);
+        assert_eq!(CategorySet::from_mask(u32::MAX).len(), 30);
+
+        let data = UnicodeData::new().unwrap();
+        assert_eq!(data.get(0x41).unwrap().category_mask(), Category::Lu.mask());
+
+        let mut set = CategorySet::from(MajorCategory::Letter);
+        assert_eq!(set.len(), 5);
+        assert!(set.contains(Category::Lo));
+        assert!(!set.contains(Category::Mn));
+
+        set.insert(Category::Mn);
+        set.remove(Category::Lo);
+        assert!(set.contains(Category::Mn));
+        assert!(!set.contains(Category::Lo));
+        assert!(set
+            .iter()
+            .eq([Category::Lu, Category::Ll, Category::Lt, Category::Mn, Category::Lm]));
+
+        assert_eq!(CategorySet::from_mask(set.to_mask()), set);
+        assert!(CategorySet::new().is_empty());
+    }
+
+    #[test]
+    fn test_bidi_base_direction() {
+        for bidi in (0..=u8::MAX).map_while(BidiCategory::decode) {
+            let expected = match bidi.abbreviation() {
+                "L" => Direction::Ltr,
+                "R" | "AL" => Direction::Rtl,
+                _ => Direction::Neutral,
+            };
+
+            assert_eq!(bidi.base_direction(), expected);
+        }
+
+        assert_eq!(BidiCategory::En.base_direction(), Direction::Neutral);
+        assert_eq!(BidiCategory::An.base_direction(), Direction::Neutral);
+        assert_eq!(BidiCategory::Lro.base_direction(), Direction::Neutral);
+    }
+
+    #[test]
+    fn test_bidi_category_groups() {
+        let bidis = (0..=u8::MAX).map_while(BidiCategory::decode);
+
+        assert!(bidis
+            .clone()
+            .filter(|bidi| bidi.is_explicit_formatting())
+            .map(BidiCategory::abbreviation)
+            .eq(["LRE", "LRO", "RLE", "RLO", "PDF"]));
+
+        assert!(bidis
+            .clone()
+            .filter(|bidi| bidi.is_isolate_initiator())
+            .map(BidiCategory::abbreviation)
+            .eq(["LRI", "RLI", "FSI"]));
+
+        assert!(bidis
+            .filter(|bidi| bidi.is_isolate())
+            .map(BidiCategory::abbreviation)
+            .eq(["LRI", "RLI", "FSI", "PDI"]));
+    }
+}
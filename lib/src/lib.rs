@@ -1,16 +1,46 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod character;
+mod prop_list;
 pub mod unicode_data;
 pub mod utf8;
 
+// The encoder used by the build script, included so that the tests can check that data
+// round-trips through the encoded format.
+#[cfg(all(test, feature = "std"))]
+#[path = "../encode.rs"]
+mod encode;
+
 pub use character::{
     BidiCategory,
     Category,
+    CategorySet,
     CharData,
     CombiningClass,
     DecompKind,
     DecompMapping,
+    Direction,
+    MajorCategory,
+    MarkPosition,
+    ParseCategoryError,
+    UcdDecompMapping,
+};
+
+pub use unicode_data::{
+    char_data,
+    char_data_why,
+    AssignedBitmap,
+    GroupInfo,
+    GroupKind,
+    NoData,
+    RangeView,
+    StaticUnicodeData,
+    UnicodeData,
+    UnicodeDataError,
 };
 
-pub use unicode_data::{StaticUnicodeData, UnicodeData};
+#[cfg(feature = "std")]
+pub use unicode_data::{CompositionTable, UnicodeSubset};
+
+#[cfg(feature = "gz")]
+pub use unicode_data::{LoadDataError, UnicodeDataOwned};
@@ -0,0 +1,370 @@
+// Binary properties from PropList.txt, which are not included in UnicodeData.txt, and the
+// composition exclusions from CompositionExclusions.txt. Each table is a sorted list of
+// non-overlapping inclusive codepoint ranges.
+//
+// These tables are taken from Unicode 15.0 and are fixed at that version. Unlike the character
+// data, they are not regenerated when the crate is built with `UTFDUMP_UCD_DIR`, so a build
+// against another Unicode version still uses the Unicode 15.0 values for these properties.
+
+pub(crate) const WHITE_SPACE: &[(u32, u32)] = &[
+    (0x0009, 0x000d),
+    (0x0020, 0x0020),
+    (0x0085, 0x0085),
+    (0x00a0, 0x00a0),
+    (0x1680, 0x1680),
+    (0x2000, 0x200a),
+    (0x2028, 0x2029),
+    (0x202f, 0x202f),
+    (0x205f, 0x205f),
+    (0x3000, 0x3000),
+];
+
+pub(crate) const DASH: &[(u32, u32)] = &[
+    (0x002d, 0x002d),
+    (0x058a, 0x058a),
+    (0x05be, 0x05be),
+    (0x1400, 0x1400),
+    (0x1806, 0x1806),
+    (0x2010, 0x2015),
+    (0x2053, 0x2053),
+    (0x207b, 0x207b),
+    (0x208b, 0x208b),
+    (0x2212, 0x2212),
+    (0x2e17, 0x2e17),
+    (0x2e1a, 0x2e1a),
+    (0x2e3a, 0x2e3b),
+    (0x2e40, 0x2e40),
+    (0x2e5d, 0x2e5d),
+    (0x301c, 0x301c),
+    (0x3030, 0x3030),
+    (0x30a0, 0x30a0),
+    (0xfe31, 0xfe32),
+    (0xfe58, 0xfe58),
+    (0xfe63, 0xfe63),
+    (0xff0d, 0xff0d),
+    (0x10ead, 0x10ead),
+];
+
+pub(crate) const HYPHEN: &[(u32, u32)] = &[
+    (0x002d, 0x002d),
+    (0x00ad, 0x00ad),
+    (0x058a, 0x058a),
+    (0x1806, 0x1806),
+    (0x2010, 0x2011),
+    (0x2e17, 0x2e17),
+    (0x30fb, 0x30fb),
+    (0xfe63, 0xfe63),
+    (0xff0d, 0xff0d),
+    (0xff65, 0xff65),
+];
+
+pub(crate) const QUOTATION_MARK: &[(u32, u32)] = &[
+    (0x0022, 0x0022),
+    (0x0027, 0x0027),
+    (0x00ab, 0x00ab),
+    (0x00bb, 0x00bb),
+    (0x2018, 0x201f),
+    (0x2039, 0x203a),
+    (0x2e42, 0x2e42),
+    (0x300c, 0x300f),
+    (0x301d, 0x301f),
+    (0xfe41, 0xfe44),
+    (0xff02, 0xff02),
+    (0xff07, 0xff07),
+    (0xff62, 0xff63),
+];
+
+pub(crate) const TERMINAL_PUNCTUATION: &[(u32, u32)] = &[
+    (0x0021, 0x0021),
+    (0x002c, 0x002c),
+    (0x002e, 0x002e),
+    (0x003a, 0x003b),
+    (0x003f, 0x003f),
+    (0x037e, 0x037e),
+    (0x0387, 0x0387),
+    (0x0589, 0x0589),
+    (0x05c3, 0x05c3),
+    (0x060c, 0x060c),
+    (0x061b, 0x061b),
+    (0x061d, 0x061f),
+    (0x06d4, 0x06d4),
+    (0x0700, 0x070a),
+    (0x070c, 0x070c),
+    (0x07f8, 0x07f9),
+    (0x0830, 0x083e),
+    (0x085e, 0x085e),
+    (0x0964, 0x0965),
+    (0x0e5a, 0x0e5b),
+    (0x0f08, 0x0f08),
+    (0x0f0d, 0x0f12),
+    (0x104a, 0x104b),
+    (0x1361, 0x1368),
+    (0x166e, 0x166e),
+    (0x16eb, 0x16ed),
+    (0x1735, 0x1736),
+    (0x17d4, 0x17d6),
+    (0x17da, 0x17da),
+    (0x1802, 0x1805),
+    (0x1808, 0x1809),
+    (0x1944, 0x1945),
+    (0x1aa8, 0x1aab),
+    (0x1b5a, 0x1b5b),
+    (0x1b5d, 0x1b5f),
+    (0x1b7d, 0x1b7e),
+    (0x1c3b, 0x1c3f),
+    (0x1c7e, 0x1c7f),
+    (0x203c, 0x203d),
+    (0x2047, 0x2049),
+    (0x2e2e, 0x2e2e),
+    (0x2e3c, 0x2e3c),
+    (0x2e41, 0x2e41),
+    (0x2e4c, 0x2e4c),
+    (0x2e4e, 0x2e4f),
+    (0x2e53, 0x2e54),
+    (0x3001, 0x3002),
+    (0xa4fe, 0xa4ff),
+    (0xa60d, 0xa60f),
+    (0xa6f3, 0xa6f7),
+    (0xa876, 0xa877),
+    (0xa8ce, 0xa8cf),
+    (0xa92f, 0xa92f),
+    (0xa9c7, 0xa9c9),
+    (0xaa5d, 0xaa5f),
+    (0xaadf, 0xaadf),
+    (0xaaf0, 0xaaf1),
+    (0xabeb, 0xabeb),
+    (0xfe50, 0xfe52),
+    (0xfe54, 0xfe57),
+    (0xff01, 0xff01),
+    (0xff0c, 0xff0c),
+    (0xff0e, 0xff0e),
+    (0xff1a, 0xff1b),
+    (0xff1f, 0xff1f),
+    (0xff61, 0xff61),
+    (0xff64, 0xff64),
+    (0x1039f, 0x1039f),
+    (0x103d0, 0x103d0),
+    (0x10857, 0x10857),
+    (0x1091f, 0x1091f),
+    (0x10a56, 0x10a57),
+    (0x10af0, 0x10af5),
+    (0x10b3a, 0x10b3f),
+    (0x10b99, 0x10b9c),
+    (0x10f55, 0x10f59),
+    (0x10f86, 0x10f89),
+    (0x11047, 0x1104d),
+    (0x110be, 0x110c1),
+    (0x11141, 0x11143),
+    (0x111c5, 0x111c6),
+    (0x111cd, 0x111cd),
+    (0x111de, 0x111df),
+    (0x11238, 0x1123c),
+    (0x112a9, 0x112a9),
+    (0x1144b, 0x1144d),
+    (0x1145a, 0x1145b),
+    (0x115c2, 0x115c5),
+    (0x115c9, 0x115d7),
+    (0x11641, 0x11642),
+    (0x1173c, 0x1173e),
+    (0x11944, 0x11944),
+    (0x11946, 0x11946),
+    (0x11a42, 0x11a43),
+    (0x11a9b, 0x11a9c),
+    (0x11aa1, 0x11aa2),
+    (0x11c41, 0x11c43),
+    (0x11c71, 0x11c71),
+    (0x11ef7, 0x11ef8),
+    (0x11f43, 0x11f44),
+    (0x12470, 0x12474),
+    (0x16a6e, 0x16a6f),
+    (0x16af5, 0x16af5),
+    (0x16b37, 0x16b39),
+    (0x16b44, 0x16b44),
+    (0x16e97, 0x16e98),
+    (0x1bc9f, 0x1bc9f),
+    (0x1da87, 0x1da8a),
+];
+
+pub(crate) const IDEOGRAPHIC: &[(u32, u32)] = &[
+    (0x3006, 0x3007),
+    (0x3021, 0x3029),
+    (0x3038, 0x303a),
+    (0x3400, 0x4dbf),
+    (0x4e00, 0x9fff),
+    (0xf900, 0xfa6d),
+    (0xfa70, 0xfad9),
+    (0x16fe4, 0x16fe4),
+    (0x17000, 0x187f7),
+    (0x18800, 0x18cd5),
+    (0x18d00, 0x18d08),
+    (0x1b170, 0x1b2fb),
+    (0x20000, 0x2a6df),
+    (0x2a700, 0x2b739),
+    (0x2b740, 0x2b81d),
+    (0x2b820, 0x2cea1),
+    (0x2ceb0, 0x2ebe0),
+    (0x2f800, 0x2fa1d),
+    (0x30000, 0x3134a),
+    (0x31350, 0x323af),
+];
+
+pub(crate) const UNIFIED_IDEOGRAPH: &[(u32, u32)] = &[
+    (0x3400, 0x4dbf),
+    (0x4e00, 0x9fff),
+    (0xfa0e, 0xfa0f),
+    (0xfa11, 0xfa11),
+    (0xfa13, 0xfa14),
+    (0xfa1f, 0xfa1f),
+    (0xfa21, 0xfa21),
+    (0xfa23, 0xfa24),
+    (0xfa27, 0xfa29),
+    (0x20000, 0x2a6df),
+    (0x2a700, 0x2b739),
+    (0x2b740, 0x2b81d),
+    (0x2b820, 0x2cea1),
+    (0x2ceb0, 0x2ebe0),
+    (0x30000, 0x3134a),
+    (0x31350, 0x323af),
+];
+
+// Characters with a canonical decomposition which are nonetheless never produced by canonical
+// composition: the script-specific and post composition version exclusions. Singleton
+// decompositions and decompositions starting with a non-starter are excluded from composition too,
+// but they are not listed here since they can be derived from UnicodeData.txt.
+pub(crate) const COMPOSITION_EXCLUSIONS: &[(u32, u32)] = &[
+    (0x0958, 0x095f),
+    (0x09dc, 0x09dd),
+    (0x09df, 0x09df),
+    (0x0a33, 0x0a33),
+    (0x0a36, 0x0a36),
+    (0x0a59, 0x0a5b),
+    (0x0a5e, 0x0a5e),
+    (0x0b5c, 0x0b5d),
+    (0x0f43, 0x0f43),
+    (0x0f4d, 0x0f4d),
+    (0x0f52, 0x0f52),
+    (0x0f57, 0x0f57),
+    (0x0f5c, 0x0f5c),
+    (0x0f69, 0x0f69),
+    (0x0f76, 0x0f76),
+    (0x0f78, 0x0f78),
+    (0x0f93, 0x0f93),
+    (0x0f9d, 0x0f9d),
+    (0x0fa2, 0x0fa2),
+    (0x0fa7, 0x0fa7),
+    (0x0fac, 0x0fac),
+    (0x0fb9, 0x0fb9),
+    (0x2adc, 0x2adc),
+    (0xfb1d, 0xfb1d),
+    (0xfb1f, 0xfb1f),
+    (0xfb2a, 0xfb36),
+    (0xfb38, 0xfb3c),
+    (0xfb3e, 0xfb3e),
+    (0xfb40, 0xfb41),
+    (0xfb43, 0xfb44),
+    (0xfb46, 0xfb4e),
+    (0x1d15e, 0x1d164),
+    (0x1d1bb, 0x1d1c0),
+];
+
+pub(crate) fn table_contains(table: &[(u32, u32)], codepoint: u32) -> bool {
+    let i = table.partition_point(|&(_, end)| end < codepoint);
+
+    table
+        .get(i)
+        .map(|&(start, _)| start <= codepoint)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        table_contains, COMPOSITION_EXCLUSIONS, DASH, HYPHEN, IDEOGRAPHIC, QUOTATION_MARK,
+        TERMINAL_PUNCTUATION, UNIFIED_IDEOGRAPH, WHITE_SPACE,
+    };
+
+    #[test]
+    fn test_tables_sorted() {
+        let tables = [
+            WHITE_SPACE,
+            DASH,
+            HYPHEN,
+            QUOTATION_MARK,
+            TERMINAL_PUNCTUATION,
+            IDEOGRAPHIC,
+            UNIFIED_IDEOGRAPH,
+            COMPOSITION_EXCLUSIONS,
+        ];
+
+        for table in tables {
+            for &(start, end) in table {
+                assert!(start <= end);
+            }
+
+            for pair in table.windows(2) {
+                assert!(pair[0].1 < pair[1].0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_white_space() {
+        for c in ['\t', '\n', '\r', ' ', '\u{85}', '\u{a0}', '\u{2000}', '\u{200a}', '\u{3000}'] {
+            assert!(table_contains(WHITE_SPACE, u32::from(c)));
+        }
+
+        for c in ['\u{8}', '\u{e}', 'a', '\u{200b}', '\u{feff}', '\u{10ffff}'] {
+            assert!(!table_contains(WHITE_SPACE, u32::from(c)));
+        }
+
+        for c in '\0'..=char::MAX {
+            assert_eq!(table_contains(WHITE_SPACE, u32::from(c)), c.is_whitespace());
+        }
+    }
+
+    #[test]
+    fn test_punctuation_properties() {
+        // U+2010 HYPHEN is both a dash and a hyphen, whereas U+2014 EM DASH is only a dash.
+        assert!(table_contains(DASH, 0x2010));
+        assert!(table_contains(HYPHEN, 0x2010));
+        assert!(table_contains(DASH, 0x2014));
+        assert!(!table_contains(HYPHEN, 0x2014));
+        assert!(table_contains(DASH, 0x2d));
+        assert!(table_contains(HYPHEN, 0x2d));
+        assert!(!table_contains(DASH, 0x5f));
+
+        for c in ['"', '\'', '\u{ab}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{300c}'] {
+            assert!(table_contains(QUOTATION_MARK, u32::from(c)));
+        }
+        assert!(!table_contains(QUOTATION_MARK, u32::from('`')));
+
+        for c in ['!', ',', '.', ':', ';', '?', '\u{3002}', '\u{11f43}'] {
+            assert!(table_contains(TERMINAL_PUNCTUATION, u32::from(c)));
+        }
+        assert!(!table_contains(TERMINAL_PUNCTUATION, u32::from('-')));
+        assert!(!table_contains(TERMINAL_PUNCTUATION, u32::from('\u{2018}')));
+    }
+
+    #[test]
+    fn test_ideograph_properties() {
+        for cp in [0x3007, 0x4e00, 0x9fff, 0xf900, 0x2f800, 0x17000, 0x2b739, 0x31350, 0x323af] {
+            assert!(table_contains(IDEOGRAPHIC, cp));
+        }
+
+        for cp in [0x4e00, 0x9fff, 0x3400, 0xfa0e, 0x2b739, 0x31350, 0x323af] {
+            assert!(table_contains(UNIFIED_IDEOGRAPH, cp));
+        }
+
+        // U+F900 is a compatibility ideograph, so it is ideographic but not a unified ideograph.
+        // U+FA0E is one of the handful of unified ideographs in the compatibility block.
+        assert!(!table_contains(UNIFIED_IDEOGRAPH, 0xf900));
+        assert!(table_contains(IDEOGRAPHIC, 0xfa0e));
+
+        for cp in [0x41, 0x3005, 0x3041, 0xac00, 0x17000, 0x2f800] {
+            assert!(!table_contains(UNIFIED_IDEOGRAPH, cp));
+        }
+
+        assert!(!table_contains(IDEOGRAPHIC, 0x3005));
+        assert!(!table_contains(IDEOGRAPHIC, 0xac00));
+    }
+}
@@ -1,4 +1,4 @@
-use core::{fmt, mem, slice, str};
+use core::{fmt, mem, ops::RangeInclusive, slice, str::{self, Chars}};
 
 use tap::Pipe;
 
@@ -9,7 +9,9 @@ use crate::character::{
     OptionalDecompKind,
     CombiningClass,
     DecompMapping,
+    BooleanProperty,
 };
+use crate::prop_list;
 
 const MAGIC_NUMBER: [u8; 8] = *b"UTFDUMP!";
 
@@ -32,9 +34,72 @@ impl UnicodeData<'static> {
     }
 }
 
+/// Returns the character data for the given character from the embedded Unicode data, or `None` if
+/// there is no data for the character. To find out why there is no data for a character, use
+/// [`char_data_why`].
+pub fn char_data(c: char) -> Option<CharData<'static>> {
+    embedded_data().get(u32::from(c))
+}
+
+/// Returns the character data for the given character from the embedded Unicode data, or the
+/// reason that there is no data for the character.
+pub fn char_data_why(c: char) -> Result<CharData<'static>, NoData> {
+    match embedded_data().get(u32::from(c)) {
+        Some(char_data) => Ok(char_data),
+        None if is_noncharacter(u32::from(c)) => Err(NoData::Noncharacter),
+        None => Err(NoData::Unassigned),
+    }
+}
+
+fn embedded_data() -> StaticUnicodeData {
+    UnicodeData::new()
+        .expect("embedded Unicode data should be valid")
+}
+
+fn is_noncharacter(codepoint: u32) -> bool {
+    (0xfdd0..=0xfdef).contains(&codepoint) || (codepoint & 0xfffe) == 0xfffe
+}
+
+fn unicode_1_name_matches(old_name: &str, name: &str) -> bool {
+    if old_name.eq_ignore_ascii_case(name) {
+        return true;
+    }
+
+    match old_name.strip_suffix(')').and_then(|old_name| old_name.split_once(" (")) {
+        Some((full, abbr)) => full.eq_ignore_ascii_case(name) || abbr.eq_ignore_ascii_case(name),
+        None => false,
+    }
+}
+
+/// The reason that there is no character data for a character. Surrogate and private-use
+/// codepoints have character data (for example, U+E000 has the name "Private Use"), so they are
+/// not represented here.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NoData {
+    /// The codepoint is one of the 66 noncharacters, which are permanently reserved for internal
+    /// use.
+    Noncharacter,
+    /// The codepoint has not been assigned a character in this version of Unicode.
+    Unassigned,
+}
+
+impl fmt::Display for NoData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Noncharacter => write!(f, "noncharacter"),
+            Self::Unassigned => write!(f, "unassigned codepoint"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoData {}
+
 impl<'a> UnicodeData<'a> {
-    pub(crate) fn from_bytes(bs: &'a [u8]) -> Result<Self, UnicodeDataError> {
-        let mut bs = ByteStream(bs);
+    /// Parses Unicode data in the encoded format produced by `data.py` and the build script. The
+    /// data embedded in the crate can be obtained with [`UnicodeData::new`] instead.
+    pub fn from_bytes(bs: &'a [u8]) -> Result<Self, UnicodeDataError> {
+        let mut bs = ByteStream::new(bs);
 
         if bs.consume(MAGIC_NUMBER.len())? != MAGIC_NUMBER {
             return Err(UnicodeDataError::InvalidHeader);
@@ -55,7 +120,315 @@ impl<'a> UnicodeData<'a> {
 
     pub fn get(self, codepoint: u32) -> Option<CharData<'a>> {
         let entry = self.char_entry_for(codepoint)?;
+        self.decode_entry(codepoint, entry)
+    }
+
+    /// Returns the character data for the first character of the given string, or `None` if the
+    /// string is empty or there is no data for the character. This looks at the first scalar
+    /// value only, not the first grapheme cluster, so for "e\u{301}" it returns the data for "e".
+    #[must_use]
+    pub fn get_first(self, s: &str) -> Option<CharData<'a>> {
+        s.chars().next().and_then(|c| self.get(u32::from(c)))
+    }
+
+    /// Returns the name of the given codepoint. This is equivalent to
+    /// `get(codepoint).map(|char_data| char_data.name())`, but is cheaper since only the name is
+    /// read from the character table entry.
+    #[must_use]
+    pub fn name(self, codepoint: u32) -> Option<&'a str> {
+        let entry = self.char_entry_for(codepoint)?;
+        self.string_table.get_u24_le(entry.name)
+    }
+
+    /// Whether there is any character data associated with the given codepoint. For repeated
+    /// queries, an [`AssignedBitmap`] may be faster.
+    #[must_use]
+    pub fn is_assigned(self, codepoint: u32) -> bool {
+        self.char_entry_for(codepoint).is_some()
+    }
+
+    /// The number of codepoints with character data. Every codepoint in a range sharing the same
+    /// character data, such as a range of CJK ideographs, is counted, and so are surrogates and
+    /// private-use characters since UnicodeData.txt lists them as ranges.
+    #[must_use]
+    pub fn len(self) -> usize {
+        let shared_len = self.group_table
+            .entries
+            .iter()
+            .filter(|entry| entry.kind == GROUP_KIND_USE_PREV_VALUE)
+            .map(|entry| entry.end.to_u32().saturating_sub(entry.start.to_u32()) as usize + 1)
+            .sum::<usize>();
+
+        self.char_table.entries.len() + shared_len
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.char_table.entries.is_empty()
+    }
+
+    /// Returns the primary composite whose canonical decomposition mapping is exactly the given
+    /// string, which is the character that canonical composition (as used by NFC) combines the
+    /// string's two characters into. There is at most one such character. Hangul syllables are
+    /// composed algorithmically from their jamo.
+    ///
+    /// Compatibility decompositions are not considered, and neither are the characters excluded
+    /// from composition: singleton decompositions such as U+212B ANGSTROM SIGN, decompositions
+    /// starting with a non-starter such as U+0344, and the characters listed in
+    /// CompositionExclusions.txt such as U+0958. The list of composition exclusions is fixed at
+    /// Unicode 15.0, regardless of the data being used.
+    ///
+    /// This searches every character, so for repeated queries a [`CompositionTable`] is faster.
+    pub fn composed_from(self, s: &str) -> impl Iterator<Item = u32> {
+        split_pair(s)
+            .and_then(|(first, second)| {
+                compose_hangul(first, second).or_else(|| {
+                    self.char_entries()
+                        .find(|&(codepoint, entry)| {
+                            self.primary_composite_pair(codepoint, entry) == Some((first, second))
+                        })
+                        .map(|(codepoint, _)| codepoint)
+                })
+            })
+            .into_iter()
+    }
+
+    /// Returns the codepoint whose Unicode 1.0 name matches the given name, ignoring ASCII case.
+    /// This is mostly useful for control characters, which have no name of their own; for example,
+    /// "LINE FEED (LF)" is the Unicode 1.0 name of U+000A.
+    pub fn lookup_unicode_1_name(self, name: &str) -> Option<u32> {
+        self.char_entries()
+            .find(|&(_, entry)| {
+                self.string_table
+                    .get_u24_le(entry.old_name)
+                    .map(|old_name| old_name.eq_ignore_ascii_case(name))
+                    .unwrap_or(false)
+            })
+            .map(|(codepoint, _)| codepoint)
+    }
+
+    /// Returns the codepoint with the given name, ignoring ASCII case. If no character has the
+    /// name, Unicode 1.0 names are searched instead, so that control characters can be found by
+    /// names such as "NULL". Unicode 1.0 names with an abbreviation in parentheses, such as
+    /// "LINE FEED (LF)", can also be found by either part alone ("LINE FEED" or "LF").
+    ///
+    /// Formal aliases from NameAliases.txt are not included in the data, so some common names for
+    /// control characters such as "TAB" are not found. Neither are the names of characters in
+    /// ranges which share the same character data, such as "CJK UNIFIED IDEOGRAPH-4E00".
+    pub fn lookup_name(self, name: &str) -> Option<u32> {
+        let by_name = self.char_entries()
+            .find(|&(codepoint, entry)| {
+                self.string_table
+                    .get_u24_le(entry.name)
+                    .map(|entry_name| {
+                        !entry_name.starts_with('<')
+                            && entry_name.eq_ignore_ascii_case(name)
+                            && self.range_sharing_data(codepoint) == Some(codepoint..=codepoint)
+                    })
+                    .unwrap_or(false)
+            })
+            .map(|(codepoint, _)| codepoint);
+
+        by_name.or_else(|| {
+            self.char_entries()
+                .find(|&(_, entry)| {
+                    self.string_table
+                        .get_u24_le(entry.old_name)
+                        .map(|old_name| unicode_1_name_matches(old_name, name))
+                        .unwrap_or(false)
+                })
+                .map(|(codepoint, _)| codepoint)
+        })
+    }
+
+    /// Returns an iterator over every codepoint with character data for which the given predicate
+    /// returns true, in ascending order of codepoint. Codepoints in ranges which share the same
+    /// character data, such as CJK ideographs, are each yielded individually.
+    pub fn iter_where<P>(self, pred: P) -> impl Iterator<Item = (u32, CharData<'a>)>
+    where
+        P: Fn(&CharData<'a>) -> bool,
+    {
+        self.char_entries()
+            .flat_map(move |(codepoint, entry)| {
+                self.range_sharing_data(codepoint)
+                    .unwrap_or(codepoint..=codepoint)
+                    .map(move |codepoint| (codepoint, entry))
+            })
+            .filter_map(move |(codepoint, entry)| {
+                let char_data = self.decode_entry(codepoint, entry)?;
+                pred(&char_data).then_some((codepoint, char_data))
+            })
+    }
+
+    /// Returns an iterator over every codepoint with the given canonical combining class, in
+    /// ascending order. This is how diacritic pickers typically group marks; for example, class
+    /// 230 (Above) contains marks such as U+0301 COMBINING ACUTE ACCENT.
+    pub fn chars_with_combining_class(
+        self,
+        ccc: CombiningClass
+    ) -> impl Iterator<Item = u32> + 'a
+    {
+        self.iter_where(move |c| c.combining_class() == ccc)
+            .map(|(codepoint, _)| codepoint)
+    }
+
+    /// Returns an iterator over every codepoint with character data, in ascending order, with each
+    /// range of codepoints sharing the same character data collapsed into a single item. This is
+    /// how code charts list ranges such as CJK ideographs and Hangul syllables. A range is
+    /// collapsed only when its codepoints share a single entry in the character table, as ranges
+    /// listed with "First" and "Last" rows in UnicodeData.txt do; the character data yielded for a
+    /// range is that of its first codepoint.
+    pub fn collapsed_ranges(self) -> impl Iterator<Item = (RangeInclusive<u32>, CharData<'a>)> {
+        self.char_entries()
+            .filter_map(move |(codepoint, entry)| {
+                let range = self.range_sharing_data(codepoint)?;
+                let char_data = self.decode_entry(codepoint, entry)?;
+                Some((range, char_data))
+            })
+    }
+
+    /// Returns the length in bytes of the combining character sequence at the start of the given
+    /// string; that is, the first character along with any following characters which have a
+    /// non-zero combining class. Returns 0 if the string is empty.
+    ///
+    /// This is not full grapheme cluster segmentation; for example, spacing marks with a combining
+    /// class of 0 are not included in the sequence.
+    #[must_use]
+    pub fn combining_sequence_len(self, s: &str) -> usize {
+        let mut chars = s.char_indices();
+
+        if chars.next().is_none() {
+            return 0;
+        }
+
+        chars
+            .find(|&(_, c)| {
+                !self.get(u32::from(c))
+                    .map(|char_data| char_data.combining_class().is_combining())
+                    .unwrap_or(false)
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(s.len())
+    }
 
+    /// Whether the character is ASCII, or recursively applying its decomposition mappings
+    /// (including compatibility mappings) produces only ASCII characters. For example, U+FB00
+    /// LATIN SMALL LIGATURE FF decomposes to "ff", so it decomposes to ASCII.
+    ///
+    /// Combining marks are not ASCII and are not removed, so characters whose decomposition
+    /// contains a combining mark do not decompose to ASCII; U+00E9 LATIN SMALL LETTER E WITH ACUTE
+    /// decomposes to "e" followed by U+0301 COMBINING ACUTE ACCENT, so it does not decompose to
+    /// ASCII.
+    pub fn decomposes_to_ascii(self, c: char) -> bool {
+        if c.is_ascii() {
+            return true;
+        }
+
+        self.get(u32::from(c))
+            .and_then(|char_data| char_data.decomp_mapping())
+            .map(|decomp| decomp.value().chars().all(|c| self.decomposes_to_ascii(c)))
+            .unwrap_or(false)
+    }
+
+    /// Whether the two strings are canonically equivalent; that is, whether their canonical
+    /// decompositions (NFD) are identical. The decompositions are compared as they are produced,
+    /// so neither string is normalized into a new buffer.
+    #[must_use]
+    pub fn canonical_eq(self, a: &str, b: &str) -> bool {
+        let mut a = CanonicalDecomposition::new(self, a);
+        let mut b = CanonicalDecomposition::new(self, b);
+
+        loop {
+            let a_segment = a.clone();
+            let b_segment = b.clone();
+            let a_len = a.skip_segment();
+            let b_len = b.skip_segment();
+
+            if a_len != b_len {
+                return false;
+            }
+
+            if a_len == 0 {
+                return true;
+            }
+
+            let a_segment = a_segment.take(a_len);
+            let b_segment = b_segment.take(b_len);
+
+            // Canonical ordering is a stable sort of each segment by combining class, so the
+            // segments are equivalent exactly when, for each combining class, the characters with
+            // that class appear in the same order in both segments. Since the segments have the
+            // same length, it is enough to check the classes which appear in the first segment.
+            let segments_eq = a_segment.clone().all(|c| {
+                let ccc = self.combining_class_of(c);
+
+                a_segment.clone()
+                    .filter(|&c| self.combining_class_of(c) == ccc)
+                    .eq(b_segment.clone().filter(|&c| self.combining_class_of(c) == ccc))
+            });
+
+            if !segments_eq {
+                return false;
+            }
+        }
+    }
+
+    /// Returns the value of the boolean property with the given name for the given codepoint, or
+    /// `None` if the property is not one supported by this crate; see [`CharData::bool_property`].
+    /// Codepoints with no character data do not have any of the supported properties.
+    #[must_use]
+    pub fn bool_property(self, codepoint: u32, property: &str) -> Option<bool> {
+        match self.get(codepoint) {
+            Some(char_data) => char_data.bool_property(property),
+            None => BooleanProperty::find(property).map(|_| false),
+        }
+    }
+
+    /// Returns the number of times canonical decomposition mappings must be applied recursively to
+    /// the character before it is fully decomposed, or 0 if it has no canonical decomposition.
+    /// For example, U+01D5 LATIN CAPITAL LETTER U WITH DIAERESIS AND MACRON decomposes to U+00DC
+    /// and U+0304, and U+00DC decomposes further, so its depth is 2.
+    ///
+    /// Hangul syllables are decomposed algorithmically: a syllable with a trailing consonant
+    /// decomposes to an LV syllable and the trailing consonant, so it has a depth of 2, and an LV
+    /// syllable has a depth of 1.
+    #[must_use]
+    pub fn decomposition_depth(self, c: char) -> usize {
+        let codepoint = u32::from(c);
+
+        if let Some(s_index) = codepoint
+            .checked_sub(HANGUL_S_BASE)
+            .filter(|&s_index| s_index < HANGUL_S_COUNT)
+        {
+            return match s_index % HANGUL_T_COUNT {
+                0 => 1,
+                _ => 2,
+            };
+        }
+
+        self.get(codepoint)
+            .and_then(|char_data| char_data.decomp_mapping())
+            .filter(|decomp| decomp.kind().is_none())
+            .map(|decomp| {
+                let max_depth = decomp.value()
+                    .chars()
+                    .map(|c| self.decomposition_depth(c))
+                    .max()
+                    .unwrap_or(0);
+
+                max_depth + 1
+            })
+            .unwrap_or(0)
+    }
+
+    fn combining_class_of(self, c: char) -> u8 {
+        self.get(u32::from(c))
+            .map(|char_data| char_data.combining_class_value())
+            .unwrap_or(0)
+    }
+
+    fn decode_entry(self, codepoint: u32, entry: &'a CharTableEntry) -> Option<CharData<'a>> {
         let flags_and_categories = entry.flags_and_categories.to_u16();
         let category = Category::decode((flags_and_categories & 0x1f) as u8)?;
         let bidi = BidiCategory::decode(((flags_and_categories >> 5) & 0x1f) as u8)?;
@@ -121,6 +494,593 @@ impl<'a> UnicodeData<'a> {
 
         self.char_table.get(index)
     }
+
+    /// Returns the two characters that the given character is composed from, if it is a primary
+    /// composite other than a Hangul syllable.
+    fn primary_composite_pair(
+        self,
+        codepoint: u32,
+        entry: &'a CharTableEntry
+    ) -> Option<(char, char)>
+    {
+        if prop_list::table_contains(prop_list::COMPOSITION_EXCLUSIONS, codepoint) {
+            return None;
+        }
+
+        let char_data = self.decode_entry(codepoint, entry)?;
+        let decomp = char_data.decomp_mapping().filter(|decomp| decomp.kind().is_none())?;
+
+        // Singleton decompositions are excluded by `split_pair`, since they have only one
+        // character.
+        let (first, second) = split_pair(decomp.value())?;
+
+        let is_starter = |codepoint| {
+            self.get(codepoint).map_or(0, |char_data| char_data.combining_class_value()) == 0
+        };
+
+        (is_starter(codepoint) && is_starter(u32::from(first))).then_some((first, second))
+    }
+
+    fn char_entries(self) -> CharEntries<'a> {
+        CharEntries {
+            groups: self.group_table.entries,
+            char_table: self.char_table,
+            index: 0,
+            offset: 0,
+        }
+    }
+
+    /// Returns the contiguous range of codepoints which share the same character data as the given
+    /// codepoint, such as a range of CJK ideographs. If the codepoint does not share its character
+    /// data with any other codepoints, the range will contain only the given codepoint. Returns
+    /// `None` if there is no character data associated with the codepoint.
+    pub fn range_sharing_data(self, codepoint: u32) -> Option<RangeInclusive<u32>> {
+        self.char_entry_for(codepoint)?;
+
+        // If the codepoint is in a `USE_PREV_VALUE` group, the range starts from the codepoint
+        // immediately before the group, whose character data the group shares.
+        if let Some(entry) = self.group_table.entry_containing(codepoint) {
+            let start = entry.start.to_u32().checked_sub(1)?;
+            return Some(start..=entry.end.to_u32());
+        }
+
+        // Otherwise, the codepoint may be the one immediately before a `USE_PREV_VALUE` group.
+        let next_entry = codepoint
+            .checked_add(1)
+            .and_then(|next| self.group_table.entry_containing(next))
+            .filter(|entry| {
+                entry.kind == GROUP_KIND_USE_PREV_VALUE
+                    && entry.start.to_u32() == codepoint + 1
+            });
+
+        match next_entry {
+            Some(entry) => Some(codepoint..=entry.end.to_u32()),
+            None => Some(codepoint..=codepoint),
+        }
+    }
+
+    /// Returns information about the group in the group table containing the given codepoint, if
+    /// any. Groups are contiguous ranges of codepoints which either have no character data, or
+    /// share the character data of the codepoint immediately before the start of the group.
+    pub fn group_for(self, codepoint: u32) -> Option<GroupInfo> {
+        self.group_table
+            .entry_containing(codepoint)
+            .map(|entry| GroupInfo {
+                start: entry.start.to_u32(),
+                end: entry.end.to_u32(),
+                kind: match entry.kind {
+                    GROUP_KIND_USE_PREV_VALUE => GroupKind::UsePrevValue,
+                    _ => GroupKind::NoValue,
+                },
+            })
+    }
+
+    /// Returns a view over the half-open range of codepoints `start..end`. The character data for
+    /// each codepoint is only looked up when it is requested from the view, but the group table is
+    /// only searched once, when the view is created.
+    pub fn range(self, start: u32, end: u32) -> RangeView<'a> {
+        let end = end.max(start);
+        let entries = self.group_table.entries;
+
+        // The groups which overlap the range. Every group before these ends before the range
+        // starts, so their total length is the same for every codepoint in the range.
+        let first = entries.partition_point(|entry| entry.end.to_u32() < start);
+        let last = entries.partition_point(|entry| entry.start.to_u32() < end).max(first);
+
+        let len_before = match entries.get(first) {
+            Some(entry) => entry.total_len_before.to_u32(),
+            None => entries.last().map_or(0, |entry| entry.total_len_through()),
+        };
+
+        RangeView {
+            data: self,
+            groups: GroupTable { entries: &entries[first..last] },
+            len_before,
+            start,
+            end,
+        }
+    }
+}
+
+const BMP_BITMAP_LEN: usize = 0x10000 / 64;
+
+/// A bitmap recording which codepoints in the Basic Multilingual Plane are assigned, so that
+/// [`AssignedBitmap::is_assigned`] can answer queries for BMP codepoints without searching the
+/// group table. Queries for codepoints outside the BMP fall back to [`UnicodeData::is_assigned`].
+///
+/// Building the bitmap requires looking up every BMP codepoint once, so it is only worthwhile when
+/// many queries will be made.
+#[derive(Clone)]
+pub struct AssignedBitmap<'a> {
+    data: UnicodeData<'a>,
+    bmp: [u64; BMP_BITMAP_LEN],
+}
+
+impl<'a> AssignedBitmap<'a> {
+    pub fn new(data: UnicodeData<'a>) -> Self {
+        let mut bmp = [0u64; BMP_BITMAP_LEN];
+
+        for codepoint in 0..0x10000 {
+            if data.is_assigned(codepoint) {
+                bmp[(codepoint / 64) as usize] |= 1 << (codepoint % 64);
+            }
+        }
+
+        Self { data, bmp }
+    }
+
+    #[must_use]
+    pub fn is_assigned(&self, codepoint: u32) -> bool {
+        match self.bmp.get((codepoint / 64) as usize) {
+            Some(word) => (word >> (codepoint % 64)) & 1 != 0,
+            None => self.data.is_assigned(codepoint),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn data(&self) -> UnicodeData<'a> {
+        self.data
+    }
+}
+
+/// Unicode data loaded at runtime from a gzip-compressed file, such as the
+/// `unicode_data_encoded.gz` file the build script decompresses. This allows the compressed data
+/// to be shipped and loaded without a build step. The decompressed data is validated when it is
+/// loaded.
+#[cfg(feature = "gz")]
+#[derive(Clone, Debug)]
+pub struct UnicodeDataOwned {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "gz")]
+impl UnicodeDataOwned {
+    pub fn from_gz_path<P>(path: P) -> Result<Self, LoadDataError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        std::fs::read(path)
+            .map_err(LoadDataError::Io)?
+            .pipe(|compressed| Self::from_gz_bytes(&compressed))
+    }
+
+    pub fn from_gz_bytes(compressed: &[u8]) -> Result<Self, LoadDataError> {
+        use std::io::Write;
+
+        let mut decoder = flate2::write::GzDecoder::new(Vec::new());
+        decoder.write_all(compressed).map_err(LoadDataError::Io)?;
+        let bytes = decoder.finish().map_err(LoadDataError::Io)?;
+
+        UnicodeData::from_bytes(&bytes).map_err(LoadDataError::InvalidData)?;
+
+        Ok(Self { bytes })
+    }
+
+    #[must_use]
+    pub fn data(&self) -> UnicodeData<'_> {
+        UnicodeData::from_bytes(&self.bytes)
+            .expect("data should have been validated when it was loaded")
+    }
+}
+
+#[cfg(feature = "gz")]
+#[derive(Debug)]
+pub enum LoadDataError {
+    Io(std::io::Error),
+    InvalidData(UnicodeDataError),
+}
+
+#[cfg(feature = "gz")]
+impl fmt::Display for LoadDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read compressed data: {}", err),
+            Self::InvalidData(err) => write!(f, "invalid data: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "gz")]
+impl std::error::Error for LoadDataError {}
+
+/// The character data for a fixed set of characters, looked up once when the subset is built so
+/// that later queries are a single hash map lookup rather than a search of the group table. This
+/// is useful when only a small, known set of characters will ever be queried.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct UnicodeSubset<'a> {
+    chars: std::collections::HashMap<char, CharData<'a>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> UnicodeSubset<'a> {
+    /// Builds a subset containing the given characters. Characters with no character data are
+    /// not included in the subset.
+    pub fn new(data: UnicodeData<'a>, chars: &[char]) -> Self {
+        let chars = chars
+            .iter()
+            .filter_map(|&c| data.get(u32::from(c)).map(|char_data| (c, char_data)))
+            .collect();
+
+        Self { chars }
+    }
+
+    #[must_use]
+    pub fn get(&self, c: char) -> Option<&CharData<'a>> {
+        self.chars.get(&c)
+    }
+
+    /// The number of characters in the subset which have character data.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+}
+
+/// An index of the primary composites in the character data, sorted by the pair of characters each
+/// one is composed from. Building the table looks at every character once, after which each query
+/// is a binary search, so this is worthwhile when composing many pairs, as NFC does.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct CompositionTable {
+    /// Each pair of characters and the primary composite they compose to, sorted by the pair.
+    pairs: Vec<((char, char), u32)>,
+}
+
+#[cfg(feature = "std")]
+impl CompositionTable {
+    pub fn new(data: UnicodeData<'_>) -> Self {
+        let mut pairs = data.char_entries()
+            .filter_map(|(codepoint, entry)| {
+                data.primary_composite_pair(codepoint, entry).map(|pair| (pair, codepoint))
+            })
+            .collect::<Vec<_>>();
+
+        pairs.sort_unstable();
+
+        Self { pairs }
+    }
+
+    /// Returns the primary composite that the two characters compose to, if any. This is
+    /// equivalent to [`UnicodeData::composed_from`] with a two-character string.
+    #[must_use]
+    pub fn compose(&self, first: char, second: char) -> Option<u32> {
+        compose_hangul(first, second).or_else(|| {
+            self.pairs
+                .binary_search_by_key(&(first, second), |&(pair, _)| pair)
+                .ok()
+                .map(|i| self.pairs[i].1)
+        })
+    }
+
+    /// Returns the primary composite whose canonical decomposition mapping is exactly the given
+    /// string. See [`UnicodeData::composed_from`].
+    #[must_use]
+    pub fn composed_from(&self, s: &str) -> Option<u32> {
+        split_pair(s).and_then(|(first, second)| self.compose(first, second))
+    }
+
+    /// The number of primary composites in the table, not counting Hangul syllables.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GroupInfo {
+    start: u32,
+    end: u32,
+    kind: GroupKind,
+}
+
+impl GroupInfo {
+    #[inline]
+    #[must_use]
+    pub fn start(self) -> u32 {
+        self.start
+    }
+
+    /// The last codepoint in the group (inclusive).
+    #[inline]
+    #[must_use]
+    pub fn end(self) -> u32 {
+        self.end
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn range(self) -> RangeInclusive<u32> {
+        self.start..=self.end
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn kind(self) -> GroupKind {
+        self.kind
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GroupKind {
+    /// The codepoints in the group have no character data.
+    NoValue,
+    /// The codepoints in the group share the character data of the codepoint immediately before
+    /// the start of the group.
+    UsePrevValue,
+}
+
+#[derive(Clone, Copy)]
+pub struct RangeView<'a> {
+    data: UnicodeData<'a>,
+    /// The groups in the group table which overlap the range.
+    groups: GroupTable<'a>,
+    /// The total length of the groups before `groups`.
+    len_before: u32,
+    start: u32,
+    end: u32,
+}
+
+impl<'a> RangeView<'a> {
+    pub fn get(self, offset: u32) -> Option<CharData<'a>> {
+        let codepoint = self.start.checked_add(offset)?;
+
+        if codepoint >= self.end {
+            return None;
+        }
+
+        let index = self.groups
+            .char_table_index_after(codepoint, self.len_before)?
+            .pipe(usize::try_from)
+            .ok()?;
+
+        let entry = self.data.char_table.get(index)?;
+        self.data.decode_entry(codepoint, entry)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(self) -> u32 {
+        self.end - self.start
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn start(self) -> u32 {
+        self.start
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn end(self) -> u32 {
+        self.end
+    }
+}
+
+const HANGUL_S_BASE: u32 = 0xac00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11a7;
+const HANGUL_L_COUNT: u32 = 19;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = HANGUL_L_COUNT * HANGUL_N_COUNT;
+
+/// Composes a leading consonant and a vowel into an LV syllable, or an LV syllable and a trailing
+/// consonant into an LVT syllable.
+fn compose_hangul(first: char, second: char) -> Option<u32> {
+    let (first, second) = (u32::from(first), u32::from(second));
+
+    let l_index = first.checked_sub(HANGUL_L_BASE).filter(|&l_index| l_index < HANGUL_L_COUNT);
+    let v_index = second.checked_sub(HANGUL_V_BASE).filter(|&v_index| v_index < HANGUL_V_COUNT);
+
+    if let (Some(l_index), Some(v_index)) = (l_index, v_index) {
+        return Some(HANGUL_S_BASE + l_index * HANGUL_N_COUNT + v_index * HANGUL_T_COUNT);
+    }
+
+    let s_index = first
+        .checked_sub(HANGUL_S_BASE)
+        .filter(|&s_index| s_index < HANGUL_S_COUNT && s_index % HANGUL_T_COUNT == 0)?;
+
+    // `HANGUL_T_BASE` itself is not a trailing consonant; a T index of 0 means there is none.
+    let t_index = second
+        .checked_sub(HANGUL_T_BASE)
+        .filter(|&t_index| 0 < t_index && t_index < HANGUL_T_COUNT)?;
+
+    Some(HANGUL_S_BASE + s_index + t_index)
+}
+
+/// Splits a string consisting of exactly two characters.
+fn split_pair(s: &str) -> Option<(char, char)> {
+    let mut chars = s.chars();
+
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(first), Some(second), None) => Some((first, second)),
+        _ => None,
+    }
+}
+
+// No character has a full canonical decomposition longer than this.
+const MAX_CANONICAL_DECOMPOSITION_LEN: usize = 4;
+
+/// Iterator over the full canonical decomposition of each character of a string, including the
+/// algorithmic decomposition of Hangul syllables. Canonical ordering is not applied.
+#[derive(Clone)]
+struct CanonicalDecomposition<'a, 's> {
+    data: UnicodeData<'a>,
+    chars: Chars<'s>,
+    buf: [char; MAX_CANONICAL_DECOMPOSITION_LEN],
+    len: usize,
+    index: usize,
+}
+
+impl<'a, 's> CanonicalDecomposition<'a, 's> {
+    fn new(data: UnicodeData<'a>, s: &'s str) -> Self {
+        Self {
+            data,
+            chars: s.chars(),
+            buf: ['\0'; MAX_CANONICAL_DECOMPOSITION_LEN],
+            len: 0,
+            index: 0,
+        }
+    }
+
+    /// Advances past the next segment of the decomposition, which is a character followed by all
+    /// of the non-starters (characters with a non-zero combining class) immediately after it.
+    /// Returns the number of characters in the segment, or 0 if the decomposition is exhausted.
+    fn skip_segment(&mut self) -> usize {
+        let mut len = 0;
+
+        loop {
+            let mut next = self.clone();
+            match next.next() {
+                Some(c) if len == 0 || self.data.combining_class_of(c) != 0 => {
+                    *self = next;
+                    len += 1;
+                },
+                _ => return len,
+            }
+        }
+    }
+
+    fn push_decomposition(&mut self, c: char) {
+        let codepoint = u32::from(c);
+
+        if let Some(s_index) = codepoint
+            .checked_sub(HANGUL_S_BASE)
+            .filter(|&s_index| s_index < HANGUL_S_COUNT)
+        {
+            let l = HANGUL_L_BASE + s_index / HANGUL_N_COUNT;
+            let v = HANGUL_V_BASE + (s_index % HANGUL_N_COUNT) / HANGUL_T_COUNT;
+            let t = HANGUL_T_BASE + s_index % HANGUL_T_COUNT;
+
+            for jamo in [l, v, t].into_iter().filter(|&jamo| jamo != HANGUL_T_BASE) {
+                self.push(char::from_u32(jamo).unwrap());
+            }
+
+            return;
+        }
+
+        let decomp = self.data
+            .get(codepoint)
+            .and_then(|char_data| char_data.decomp_mapping())
+            .filter(|decomp| decomp.kind().is_none());
+
+        match decomp {
+            Some(decomp) => {
+                for c in decomp.value().chars() {
+                    self.push_decomposition(c);
+                }
+            },
+            None => self.push(c),
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        self.buf[self.len] = c;
+        self.len += 1;
+    }
+}
+
+impl<'a, 's> Iterator for CanonicalDecomposition<'a, 's> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            let c = self.chars.next()?;
+            self.len = 0;
+            self.index = 0;
+            self.push_decomposition(c);
+        }
+
+        let c = self.buf[self.index];
+        self.index += 1;
+        Some(c)
+    }
+}
+
+/// Iterator over every entry in the character table, along with the codepoint of each entry. For a
+/// range of codepoints sharing the same entry, only the first codepoint of the range is yielded.
+struct CharEntries<'a> {
+    groups: &'a [GroupTableEntry],
+    char_table: CharTable<'a>,
+    index: usize,
+    offset: u32,
+}
+
+impl<'a> Iterator for CharEntries<'a> {
+    type Item = (u32, &'a CharTableEntry);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.char_table.get(self.index)?;
+
+        let mut codepoint = u32::try_from(self.index)
+            .ok()?
+            .checked_add(self.offset)?;
+
+        // The codepoint of the entry is its index plus the total length of all of the groups
+        // before it, so skip over any groups which start at or before the codepoint we have
+        // calculated so far.
+        while let Some(group) = self.groups.first() {
+            let start = group.start.to_u32();
+            let end = group.end.to_u32();
+
+            if start > codepoint {
+                break;
+            }
+
+            let group_len = end.checked_sub(start)?.checked_add(1)?;
+            self.offset = self.offset.checked_add(group_len)?;
+            codepoint = codepoint.checked_add(group_len)?;
+            self.groups = &self.groups[1..];
+        }
+
+        self.index += 1;
+
+        Some((codepoint, entry))
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -131,7 +1091,10 @@ struct GroupTable<'a> {
 impl<'a> GroupTable<'a> {
     fn new(bs: &'a [u8]) -> Result<Self, UnicodeDataError> {
         if bs.len() % GroupTableEntry::SIZE != 0 {
-            return Err(UnicodeDataError::InvalidTableSize);
+            return Err(UnicodeDataError::InvalidTableSize {
+                len: bs.len(),
+                entry_size: GroupTableEntry::SIZE,
+            });
         }
 
         let num_entries = bs.len() / GroupTableEntry::SIZE;
@@ -165,6 +1128,49 @@ impl<'a> GroupTable<'a> {
         Ok(Self { entries })
     }
 
+    fn entry_containing(self, codepoint: u32) -> Option<&'a GroupTableEntry> {
+        let i = self.entries.partition_point(|entry| entry.end.to_u32() < codepoint);
+
+        self.entries
+            .get(i)
+            .filter(|entry| entry.start.to_u32() <= codepoint)
+    }
+
+    /// Finds the character table index for the given codepoint by stepping forward through the
+    /// groups in order, given the total length of the groups before the first one. This is used
+    /// by [`RangeView`], which only holds the few groups overlapping its range.
+    fn char_table_index_after(self, codepoint: u32, len_before: u32) -> Option<u32> {
+        let mut offset = len_before;
+
+        for entry in self.entries {
+            let start = entry.start.to_u32();
+            let end = entry.end.to_u32();
+
+            // The groups are sorted, so no later group can contain the codepoint either.
+            if codepoint < start {
+                break;
+            }
+
+            if codepoint <= end {
+                return match entry.kind {
+                    // See `char_table_index_for` for how the index is calculated.
+                    GROUP_KIND_USE_PREV_VALUE => start
+                        .checked_sub(1)
+                        .expect("first codepoint for a USE_PREV_VALUE group should always be at least 1")
+                        .checked_sub(entry.total_len_before.to_u32())
+                        .expect("computed character data index should not underflow")
+                        .pipe(Some),
+
+                    _ => None,
+                };
+            }
+
+            offset = entry.total_len_through();
+        }
+
+        codepoint.checked_sub(offset)
+    }
+
     // TODO: compare performance of binary search to linear search
     // TODO: fast path for characters before the first group
     fn char_table_index_for(self, codepoint: u32) -> Option<u32> {
@@ -242,6 +1248,21 @@ struct GroupTableEntry {
 
 impl GroupTableEntry {
     const SIZE: usize = mem::size_of::<Self>();
+
+    /// The total length of all groups up to and including this one.
+    fn total_len_through(&self) -> u32 {
+        let group_len = self.end
+            .to_u32()
+            .checked_sub(self.start.to_u32())
+            .expect("group start should be less than or equal to the group end")
+            .checked_add(1)
+            .expect("group length should not overflow a u32");
+
+        self.total_len_before
+            .to_u32()
+            .checked_add(group_len)
+            .expect("cumulative group length should not overflow a u32")
+    }
 }
 
 #[derive(Debug)]
@@ -253,7 +1274,10 @@ struct CharTable<'a> {
 impl<'a> CharTable<'a> {
     fn new(bs: &'a [u8]) -> Result<Self, UnicodeDataError> {
         if bs.len() % CharTableEntry::SIZE != 0 {
-            return Err(UnicodeDataError::InvalidTableSize);
+            return Err(UnicodeDataError::InvalidTableSize {
+                len: bs.len(),
+                entry_size: CharTableEntry::SIZE,
+            });
         }
 
         let num_entries = bs.len() / CharTableEntry::SIZE;
@@ -397,60 +1421,104 @@ impl fmt::Debug for U32Le {
     }
 }
 
-struct ByteStream<'a>(&'a [u8]);
+struct ByteStream<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
 
 impl<'a> ByteStream<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
     fn consume(&mut self, n: usize) -> Result<&'a [u8], UnicodeDataError> {
-        if n > self.0.len() {
-            return Err(UnicodeDataError::InsufficientBytes);
+        if n > self.bytes.len() {
+            return Err(UnicodeDataError::InsufficientBytes {
+                needed: n,
+                available: self.bytes.len(),
+                at: self.offset,
+            });
         }
 
-        let consumed = &self.0[..n];
-        self.0 = &self.0[n..];
+        let consumed = &self.bytes[..n];
+        self.bytes = &self.bytes[n..];
+        self.offset += n;
         Ok(consumed)
     }
 
     fn consume_4_byte_len(&mut self) -> Result<usize, UnicodeDataError> {
+        let at = self.offset;
+
         self.consume(4)?
             .pipe(<[u8; 4]>::try_from)
             .unwrap()
             .pipe(u32::from_le_bytes)
             .pipe(usize::try_from)
-            .map_err(|_| UnicodeDataError::OutOfBounds)
+            .map_err(|_| UnicodeDataError::OutOfBounds { at })
     }
 
     fn check_empty(&self) -> Result<(), UnicodeDataError> {
-        self.0
+        self.bytes
             .is_empty()
             .then_some(())
-            .ok_or(UnicodeDataError::LeftoverBytes)
+            .ok_or(UnicodeDataError::LeftoverBytes {
+                count: self.bytes.len(),
+                at: self.offset,
+            })
     }
 }
 
 #[derive(Debug)]
 pub enum UnicodeDataError {
     InvalidHeader,
-    InsufficientBytes,
-    OutOfBounds,
-    LeftoverBytes,
-    InvalidTableSize,
+    InsufficientBytes { needed: usize, available: usize, at: usize },
+    OutOfBounds { at: usize },
+    LeftoverBytes { count: usize, at: usize },
+    InvalidTableSize { len: usize, entry_size: usize },
 }
 
 impl fmt::Display for UnicodeDataError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InvalidHeader => write!(f, "invalid header"),
-            Self::InsufficientBytes => write!(f, "fewer bytes than expected"),
-            Self::OutOfBounds => write!(f, "index out of bounds"),
-            Self::LeftoverBytes => write!(f, "unexpected bytes found after expected end of data"),
-            Self::InvalidTableSize => write!(f, "invalid table size"),
+            Self::InsufficientBytes { needed, available, at } => write!(
+                f,
+                "fewer bytes than expected at offset {}: needed {}, but only {} available",
+                at, needed, available
+            ),
+            Self::OutOfBounds { at } => write!(f, "index out of bounds at offset {}", at),
+            Self::LeftoverBytes { count, at } => write!(
+                f,
+                "{} unexpected bytes found after expected end of data at offset {}",
+                count, at
+            ),
+            Self::InvalidTableSize { len, entry_size } => write!(
+                f,
+                "invalid table size: {} bytes is not a multiple of the entry size {}",
+                len, entry_size
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for UnicodeDataError {}
+
 #[cfg(test)]
 mod tests {
-    use super::UnicodeData;
+    use super::{
+        char_data,
+        char_data_why,
+        AssignedBitmap,
+        CanonicalDecomposition,
+        Category,
+        CombiningClass,
+        GroupKind,
+        NoData,
+        UnicodeData,
+        UnicodeDataError,
+        MAGIC_NUMBER,
+    };
 
     #[test]
     fn test_data_decode() {
@@ -478,4 +1546,744 @@ mod tests {
 
         assert_eq!(data.get(0x1039f).unwrap().name(), "UGARITIC WORD DIVIDER");
     }
+
+    #[test]
+    fn test_data_decode_errors() {
+        assert!(matches!(
+            UnicodeData::from_bytes(b"NOTMAGIC"),
+            Err(UnicodeDataError::InvalidHeader)
+        ));
+
+        assert!(matches!(
+            UnicodeData::from_bytes(b"UTF"),
+            Err(UnicodeDataError::InsufficientBytes { needed: 8, available: 3, at: 0 })
+        ));
+
+        let mut bs = MAGIC_NUMBER.to_vec();
+        bs.extend_from_slice(&[0; 8]);
+        assert!(matches!(
+            UnicodeData::from_bytes(&bs),
+            Err(UnicodeDataError::InsufficientBytes { needed: 4, available: 0, at: 16 })
+        ));
+
+        let mut bs = MAGIC_NUMBER.to_vec();
+        bs.extend_from_slice(&[0; 12]);
+        bs.extend_from_slice(&[0xff; 2]);
+        assert!(matches!(
+            UnicodeData::from_bytes(&bs),
+            Err(UnicodeDataError::LeftoverBytes { count: 2, at: 20 })
+        ));
+
+        let mut bs = MAGIC_NUMBER.to_vec();
+        bs.extend_from_slice(&[1, 0, 0, 0]);
+        bs.extend_from_slice(&[0; 8]);
+        bs.push(0);
+        assert!(matches!(
+            UnicodeData::from_bytes(&bs),
+            Err(UnicodeDataError::InvalidTableSize { len: 1, entry_size: 13 })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_data_error_is_std_error() {
+        fn load(bs: &[u8]) -> Result<UnicodeData<'_>, Box<dyn std::error::Error>> {
+            Ok(UnicodeData::from_bytes(bs)?)
+        }
+
+        let err = load(b"NOTMAGIC").err().unwrap();
+        assert_eq!(err.to_string(), "invalid header");
+    }
+
+    #[test]
+    fn test_range_sharing_data() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.range_sharing_data(0x3400), Some(0x3400..=0x4dbf));
+        assert_eq!(data.range_sharing_data(0x3401), Some(0x3400..=0x4dbf));
+        assert_eq!(data.range_sharing_data(0x4dbf), Some(0x3400..=0x4dbf));
+        assert_eq!(data.range_sharing_data(0x33ff), Some(0x33ff..=0x33ff));
+        assert_eq!(data.range_sharing_data(0x4dc0), Some(0x4dc0..=0x4dc0));
+        assert_eq!(data.range_sharing_data(0x41), Some(0x41..=0x41));
+        assert_eq!(data.range_sharing_data(0x378), None);
+    }
+
+    #[test]
+    fn test_char_entries() {
+        let data = UnicodeData::new().unwrap();
+
+        for (codepoint, entry) in data.char_entries() {
+            assert!(core::ptr::eq(entry, data.char_entry_for(codepoint).unwrap()));
+        }
+
+        let codepoints = data.char_entries()
+            .map(|(codepoint, _)| codepoint)
+            .skip_while(|&codepoint| codepoint < 0x376)
+            .take(3);
+
+        assert!(codepoints.eq([0x376, 0x377, 0x37a]));
+    }
+
+    const COMPOSED_FROM_CASES: &[(&str, Option<u32>)] = &[
+        ("e\u{301}", Some(0xe9)),
+        ("A\u{30a}", Some(0xc5)),
+        ("\u{dc}\u{304}", Some(0x1d5)),
+        // Singleton decompositions are never composed to.
+        ("\u{c5}", None),
+        ("\u{3b9}", None),
+        // U+0344 decomposes to two non-starters.
+        ("\u{308}\u{301}", None),
+        // U+0958 is listed in CompositionExclusions.txt.
+        ("\u{915}\u{93c}", None),
+        // Hangul syllables.
+        ("\u{1100}\u{1161}", Some(0xac00)),
+        ("\u{1112}\u{1175}", Some(0xd788)),
+        ("\u{ac00}\u{11a8}", Some(0xac01)),
+        ("\u{d788}\u{11c2}", Some(0xd7a3)),
+        ("\u{ac00}\u{11a7}", None),
+        ("\u{ac01}\u{11a8}", None),
+        ("\u{1100}\u{11a8}", None),
+        ("ff", None),
+        ("e\u{301}\u{301}", None),
+        ("e", None),
+        ("", None),
+    ];
+
+    #[test]
+    fn test_composed_from() {
+        let data = UnicodeData::new().unwrap();
+
+        for &(s, composite) in COMPOSED_FROM_CASES {
+            assert!(data.composed_from(s).eq(composite), "{:?}", s);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_composition_table() {
+        use super::{CompositionTable, HANGUL_S_BASE, HANGUL_S_COUNT};
+
+        let data = UnicodeData::new().unwrap();
+        let table = CompositionTable::new(data);
+
+        for &(s, composite) in COMPOSED_FROM_CASES {
+            assert_eq!(table.composed_from(s), composite, "{:?}", s);
+        }
+
+        // The number of primary composites in Unicode 15.0, not counting Hangul syllables.
+        assert_eq!(table.len(), 941);
+
+        for &((first, second), composite) in &table.pairs {
+            let decomp = data.get(composite).unwrap().decomp_mapping().unwrap();
+            assert!(decomp.value().chars().eq([first, second]));
+            assert_eq!(table.compose(first, second), Some(composite));
+        }
+
+        // Every Hangul syllable composes from its own decomposition.
+        for codepoint in HANGUL_S_BASE..(HANGUL_S_BASE + HANGUL_S_COUNT) {
+            let mut buf = [0u8; 4];
+            let c = char::from_u32(codepoint).unwrap();
+            let mut jamo = CanonicalDecomposition::new(data, c.encode_utf8(&mut buf));
+            let first = u32::from(jamo.next().unwrap());
+            let composed = jamo.fold(first, |composed, c| {
+                table.compose(char::from_u32(composed).unwrap(), c).unwrap()
+            });
+            assert_eq!(composed, codepoint);
+        }
+    }
+
+    #[test]
+    fn test_decomposes_to_ascii() {
+        let data = UnicodeData::new().unwrap();
+
+        assert!(data.decomposes_to_ascii('a'));
+        assert!(data.decomposes_to_ascii('\u{fb00}'));
+        assert!(data.decomposes_to_ascii('\u{2460}'));
+        assert!(data.decomposes_to_ascii('\u{ff21}'));
+        assert!(!data.decomposes_to_ascii('\u{e9}'));
+        assert!(!data.decomposes_to_ascii('\u{3b1}'));
+        assert!(!data.decomposes_to_ascii('\u{378}'));
+    }
+
+    #[test]
+    fn test_assigned_bitmap() {
+        let data = UnicodeData::new().unwrap();
+        let bitmap = AssignedBitmap::new(data);
+
+        for codepoint in 0..=0x10ffff {
+            assert_eq!(bitmap.is_assigned(codepoint), data.is_assigned(codepoint));
+        }
+
+        assert!(bitmap.is_assigned(0x41));
+        assert!(bitmap.is_assigned(0x4e00));
+        assert!(!bitmap.is_assigned(0x378));
+        assert!(bitmap.is_assigned(0x1039f));
+        assert!(!bitmap.is_assigned(0x110000));
+    }
+
+    #[test]
+    fn test_combining_sequence_len() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.combining_sequence_len(""), 0);
+        assert_eq!(data.combining_sequence_len("a"), 1);
+        assert_eq!(data.combining_sequence_len("ab"), 1);
+        assert_eq!(data.combining_sequence_len("a\u{301}b"), 3);
+        assert_eq!(data.combining_sequence_len("a\u{301}\u{323}"), 5);
+        assert_eq!(data.combining_sequence_len("\u{301}a"), 2);
+        assert_eq!(data.combining_sequence_len("\u{1f600}\u{301}"), 6);
+    }
+
+    #[test]
+    fn test_group_for() {
+        let data = UnicodeData::new().unwrap();
+
+        let group = data.group_for(0x3401).unwrap();
+        assert_eq!(group.range(), 0x3401..=0x4dbf);
+        assert_eq!(group.kind(), GroupKind::UsePrevValue);
+        assert_eq!(data.group_for(0x4dbf), Some(group));
+
+        let group = data.group_for(0x378).unwrap();
+        assert_eq!(group.range(), 0x378..=0x379);
+        assert_eq!(group.kind(), GroupKind::NoValue);
+
+        assert!(data.group_for(0x3400).is_none());
+        assert!(data.group_for(0x41).is_none());
+    }
+
+    #[test]
+    fn test_char_data_why() {
+        assert_eq!(char_data('a').unwrap().name(), "LATIN SMALL LETTER A");
+        assert!(char_data('\u{378}').is_none());
+
+        assert_eq!(char_data_why('a').unwrap().name(), "LATIN SMALL LETTER A");
+        assert_eq!(char_data_why('\u{e000}').unwrap().name(), "Private Use");
+        assert_eq!(char_data_why('\u{378}').err(), Some(NoData::Unassigned));
+        assert_eq!(char_data_why('\u{fdd0}').err(), Some(NoData::Noncharacter));
+        assert_eq!(char_data_why('\u{fffe}').err(), Some(NoData::Noncharacter));
+        assert_eq!(char_data_why('\u{10ffff}').err(), Some(NoData::Noncharacter));
+    }
+
+    #[test]
+    fn test_range_view() {
+        let data = UnicodeData::new().unwrap();
+
+        let view = data.range(0x376, 0x37b);
+        assert_eq!(view.len(), 5);
+        assert!(!view.is_empty());
+        assert_eq!(view.get(1).unwrap().name(), "GREEK SMALL LETTER PAMPHYLIAN DIGAMMA");
+        assert!(view.get(2).is_none());
+        assert!(view.get(3).is_none());
+        assert_eq!(view.get(4).unwrap().name(), "GREEK YPOGEGRAMMENI");
+        assert!(view.get(5).is_none());
+        assert!(view.get(u32::MAX).is_none());
+
+        let view = data.range(0x3400, 0x3400);
+        assert_eq!(view.len(), 0);
+        assert!(view.is_empty());
+        assert!(view.get(0).is_none());
+
+        let view = data.range(0x10, 0x5);
+        assert!(view.is_empty());
+    }
+
+    #[test]
+    fn test_range_view_matches_get() {
+        let data = UnicodeData::new().unwrap();
+
+        let check_range = |start: u32, end: u32| {
+            let view = data.range(start, end);
+            for offset in 0..(end - start) {
+                let codepoint = start + offset;
+                assert_eq!(
+                    view.get(offset).map(|char_data| (char_data.codepoint(), char_data.name())),
+                    data.get(codepoint).map(|char_data| (char_data.codepoint(), char_data.name())),
+                    "range {:#x}..{:#x}, codepoint {:#x}",
+                    start,
+                    end,
+                    codepoint
+                );
+            }
+        };
+
+        // Ranges spanning many groups, including the `USE_PREV_VALUE` groups for CJK ideographs
+        // and Hangul syllables, and the end of the codespace.
+        check_range(0x0, 0x3000);
+        check_range(0x3300, 0x4e10);
+        check_range(0xabf0, 0xd800);
+        check_range(0x1f000, 0x20010);
+        check_range(0x10fff0, 0x110000);
+
+        // Small ranges starting either side of each group's start and end.
+        for entry in data.group_table.entries {
+            let (start, end) = (entry.start.to_u32(), entry.end.to_u32());
+            check_range(start.saturating_sub(3), (start + 3).min(0x110000));
+            check_range(start, (start + 1).min(0x110000));
+            check_range(end.saturating_sub(2), (end + 3).min(0x110000));
+            check_range(end + 1, (end + 4).min(0x110000).max(end + 1));
+        }
+    }
+
+    #[test]
+    fn test_canonical_eq() {
+        let data = UnicodeData::new().unwrap();
+
+        assert!(data.canonical_eq("", ""));
+        assert!(data.canonical_eq("abc", "abc"));
+        assert!(!data.canonical_eq("abc", "abd"));
+        assert!(!data.canonical_eq("a", ""));
+        assert!(!data.canonical_eq("e", "e\u{301}"));
+
+        assert!(data.canonical_eq("\u{e9}", "e\u{301}"));
+        assert!(data.canonical_eq("caf\u{e9}s", "cafe\u{301}s"));
+        assert!(data.canonical_eq("\u{212b}", "\u{c5}"));
+        assert!(data.canonical_eq("\u{212b}", "A\u{30a}"));
+
+        // Combining marks with different combining classes may be reordered, but marks with the
+        // same combining class may not.
+        assert!(data.canonical_eq("\u{1e0b}\u{323}", "\u{1e0d}\u{307}"));
+        assert!(data.canonical_eq("d\u{307}\u{323}", "d\u{323}\u{307}"));
+        assert!(!data.canonical_eq("a\u{301}\u{300}", "a\u{300}\u{301}"));
+        assert!(!data.canonical_eq("d\u{323}x\u{307}", "d\u{307}x\u{323}"));
+
+        assert!(data.canonical_eq("\u{d4db}", "\u{1111}\u{1171}\u{11b6}"));
+        assert!(data.canonical_eq("\u{ac00}", "\u{1100}\u{1161}"));
+
+        // Compatibility decompositions do not make strings canonically equivalent.
+        assert!(!data.canonical_eq("\u{fb00}", "ff"));
+    }
+
+    #[test]
+    fn test_canonical_decomposition_len() {
+        let data = UnicodeData::new().unwrap();
+        let mut buf = [0u8; 4];
+
+        // Decomposing any character must not overflow the decomposition buffer.
+        for c in '\0'..=char::MAX {
+            CanonicalDecomposition::new(data, c.encode_utf8(&mut buf)).for_each(drop);
+        }
+
+        assert!(CanonicalDecomposition::new(data, "\u{1f82}")
+            .eq(['\u{3b1}', '\u{313}', '\u{300}', '\u{345}']));
+    }
+
+    #[test]
+    fn test_chars_with_combining_class() {
+        let data = UnicodeData::new().unwrap();
+
+        assert!(data
+            .chars_with_combining_class(CombiningClass(233))
+            .eq([0x35c, 0x35f, 0x362, 0x1dfc]));
+
+        let mut above = data.chars_with_combining_class(CombiningClass(230));
+        assert_eq!(above.next(), Some(0x300));
+        assert_eq!(above.next(), Some(0x301));
+        assert_eq!(above.count(), 508);
+
+        assert_eq!(data.chars_with_combining_class(CombiningClass(1)).count(), 32);
+        assert_eq!(data.chars_with_combining_class(CombiningClass(255)).count(), 0);
+    }
+
+    #[cfg(feature = "gz")]
+    #[test]
+    fn test_unicode_data_owned_from_gz() {
+        use std::io::Write;
+
+        use super::{LoadDataError, UnicodeDataOwned, UNICODE_DATA_BYTES};
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(UNICODE_DATA_BYTES).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let owned = UnicodeDataOwned::from_gz_bytes(&compressed).unwrap();
+        assert_eq!(owned.data().get(0x41).unwrap().name(), "LATIN CAPITAL LETTER A");
+        assert_eq!(owned.data().len(), UnicodeData::new().unwrap().len());
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(b"not unicode data").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert!(matches!(
+            UnicodeDataOwned::from_gz_bytes(&compressed),
+            Err(LoadDataError::InvalidData(UnicodeDataError::InvalidHeader))
+        ));
+    }
+
+    #[test]
+    fn test_collapsed_ranges() {
+        let data = UnicodeData::new().unwrap();
+
+        let mut ranges = data.collapsed_ranges();
+        let (range, char_data) = ranges.next().unwrap();
+        assert_eq!(range, 0x0..=0x0);
+        assert_eq!(char_data.codepoint(), 0x0);
+
+        let (range, char_data) = data
+            .collapsed_ranges()
+            .find(|(range, _)| range.contains(&0x4e00))
+            .unwrap();
+        assert_eq!(range, 0x4e00..=0x9fff);
+        assert_eq!(char_data.name(), "CJK Ideograph");
+
+        let (range, _) = data
+            .collapsed_ranges()
+            .find(|(range, _)| range.contains(&0xac01))
+            .unwrap();
+        assert_eq!(range, 0xac00..=0xd7a3);
+
+        assert_eq!(
+            data.collapsed_ranges().map(|(range, _)| range.count()).sum::<usize>(),
+            data.len()
+        );
+
+        assert!(data
+            .collapsed_ranges()
+            .zip(data.collapsed_ranges().skip(1))
+            .all(|((a, _), (b, _))| a.end() < b.start()));
+    }
+
+    #[test]
+    fn test_get_first() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.get_first("abc").unwrap().codepoint(), 0x61);
+        assert_eq!(data.get_first("e\u{301}").unwrap().codepoint(), 0x65);
+        assert_eq!(data.get_first("\u{1f600}").unwrap().codepoint(), 0x1f600);
+        assert!(data.get_first("").is_none());
+        assert!(data.get_first("\u{378}").is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_encode_round_trip() {
+        use crate::{encode::encode_unicode_data, BidiCategory, DecompKind};
+
+        let input = "\
+000A;<control>;Cc;0;B;;;;;N;LINE FEED (LF);;;;
+0028;LEFT PARENTHESIS;Ps;0;ON;;;;;Y;OPENING PARENTHESIS;;;;
+0031;DIGIT ONE;Nd;0;EN;;1;1;1;N;;;;;
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+00BD;VULGAR FRACTION ONE HALF;No;0;ON;<fraction> 0031 2044 0032;;;1/2;N;FRACTION ONE HALF;;;;
+00C5;LATIN CAPITAL LETTER A WITH RING ABOVE;Lu;0;L;0041 030A;;;;N;;;;00E5;
+01C5;LATIN CAPITAL LETTER D WITH SMALL LETTER Z WITH CARON;Lt;0;L;<compat> 0044 017E;;;;N;;;01C4;01C6;01C5
+0301;COMBINING ACUTE ACCENT;Mn;230;NSM;;;;;N;NON-SPACING ACUTE;Oxia, Tonos;;;
+4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;
+9FFF;<CJK Ideograph, Last>;Lo;0;L;;;;;N;;;;;
+A000;YI SYLLABLE IT;Lo;0;L;;;;;N;;;;;
+";
+
+        let encoded = encode_unicode_data(input).unwrap();
+        let data = UnicodeData::from_bytes(&encoded).unwrap();
+
+        assert_eq!(data.len(), 10 + (0x9fff - 0x4e00));
+
+        let lf = data.get(0xa).unwrap();
+        assert_eq!(lf.name(), "<control>");
+        assert_eq!(lf.category(), Category::Cc);
+        assert_eq!(lf.bidi_category(), BidiCategory::B);
+        assert_eq!(lf.unicode_1_name(), Some("LINE FEED (LF)"));
+
+        let paren = data.get(0x28).unwrap();
+        assert!(paren.mirrored());
+        assert!(!lf.mirrored());
+
+        let one = data.get(0x31).unwrap();
+        assert_eq!(one.decimal_digit_value(), Some(1));
+        assert_eq!(one.digit_value(), Some(1));
+        assert_eq!(one.numeric_value(), Some("1"));
+        assert_eq!(one.bidi_category(), BidiCategory::En);
+
+        let a = data.get(0x41).unwrap();
+        assert_eq!(a.name(), "LATIN CAPITAL LETTER A");
+        assert_eq!(a.lowercase(), Some("a"));
+        assert_eq!(a.uppercase(), None);
+        assert_eq!(a.decimal_digit_value(), None);
+        assert_eq!(a.numeric_value(), None);
+        assert_eq!(a.unicode_1_name(), None);
+        assert!(a.decomp_mapping().is_none());
+
+        let half = data.get(0xbd).unwrap();
+        let decomp = half.decomp_mapping().unwrap();
+        assert_eq!(decomp.kind(), Some(DecompKind::Fraction));
+        assert_eq!(decomp.value(), "1\u{2044}2");
+        assert_eq!(half.numeric_value(), Some("1/2"));
+
+        let a_ring = data.get(0xc5).unwrap();
+        let decomp = a_ring.decomp_mapping().unwrap();
+        assert_eq!(decomp.kind(), None);
+        assert_eq!(decomp.value(), "A\u{30a}");
+
+        let dz = data.get(0x1c5).unwrap();
+        assert_eq!(dz.category(), Category::Lt);
+        assert_eq!(dz.uppercase(), Some("\u{1c4}"));
+        assert_eq!(dz.lowercase(), Some("\u{1c6}"));
+        assert_eq!(dz.titlecase(), Some("\u{1c5}"));
+        assert_eq!(dz.decomp_mapping().unwrap().kind(), Some(DecompKind::Compat));
+
+        let acute = data.get(0x301).unwrap();
+        assert_eq!(acute.combining_class(), CombiningClass(230));
+        assert_eq!(acute.bidi_category(), BidiCategory::Nsm);
+        assert_eq!(acute.comment(), Some("Oxia, Tonos"));
+
+        // Every codepoint in the shared range has the data from its "First" row.
+        for codepoint in [0x4e00, 0x7000, 0x9fff] {
+            let ideograph = data.get(codepoint).unwrap();
+            assert_eq!(ideograph.codepoint(), codepoint);
+            assert_eq!(ideograph.name(), "CJK Ideograph");
+            assert_eq!(ideograph.category(), Category::Lo);
+        }
+        assert_eq!(data.range_sharing_data(0x7000), Some(0x4e00..=0x9fff));
+
+        // The codepoint after the shared range maps to its own entry.
+        assert_eq!(data.get(0xa000).unwrap().name(), "YI SYLLABLE IT");
+
+        for codepoint in [0x0, 0x42, 0x302, 0x4dff, 0xa001, 0x10ffff] {
+            assert!(data.get(codepoint).is_none());
+        }
+    }
+
+    // Checks that every value of each property appears somewhere in the embedded data, to catch
+    // regeneration bugs which leave a column zeroed or truncated.
+    #[test]
+    fn test_property_value_coverage() {
+        use crate::{BidiCategory, CategorySet, DecompKind};
+
+        const DECOMP_KINDS: [DecompKind; 16] = [
+            DecompKind::Nobreak, DecompKind::Compat, DecompKind::Super, DecompKind::Fraction,
+            DecompKind::Sub, DecompKind::Font, DecompKind::Circle, DecompKind::Wide,
+            DecompKind::Vertical, DecompKind::Square, DecompKind::Isolated, DecompKind::Final,
+            DecompKind::Initial, DecompKind::Medial, DecompKind::Small, DecompKind::Narrow,
+        ];
+
+        let data = UnicodeData::new().unwrap();
+
+        let mut categories = CategorySet::new();
+        let mut bidis = 0u32;
+        let mut decomp_kinds = 0u32;
+        let mut combining_classes = [false; 256];
+        let mut mirrored = false;
+        let mut fields = [false; 8];
+        let mut decimal_digits = 0u16;
+        let mut digits = 0u16;
+
+        for (_, c) in data.collapsed_ranges() {
+            categories.insert(c.category());
+            bidis |= 1 << c.bidi_category().to_byte();
+            combining_classes[usize::from(c.combining_class_value())] = true;
+            mirrored |= c.mirrored();
+
+            if let Some(kind) = c.decomp_mapping().and_then(|decomp| decomp.kind()) {
+                decomp_kinds |= 1 << DECOMP_KINDS.iter().position(|&k| k == kind).unwrap();
+            }
+
+            let present = [
+                c.decomp_mapping().is_some(),
+                c.decimal_digit_value().is_some(),
+                c.digit_value().is_some(),
+                c.numeric_value().is_some(),
+                c.unicode_1_name().is_some(),
+                c.uppercase().is_some(),
+                c.lowercase().is_some(),
+                c.titlecase().is_some(),
+            ];
+
+            for (field, present) in fields.iter_mut().zip(present) {
+                *field |= present;
+            }
+
+            if let Some(decimal_digit) = c.decimal_digit_value() {
+                decimal_digits |= 1 << decimal_digit;
+                assert_eq!(c.digit_value(), Some(decimal_digit));
+            }
+
+            if let Some(digit) = c.digit_value() {
+                digits |= 1 << digit;
+                assert!(c.numeric_value()
+                    .and_then(|numeric| numeric.parse::<u8>().ok())
+                    .is_some_and(|numeric| numeric == digit));
+            }
+        }
+
+        // UnicodeData.txt does not list unassigned codepoints, so `Cn` never appears.
+        assert!(Category::all()
+            .filter(|&category| category != Category::Cn)
+            .all(|category| categories.contains(category)));
+        assert!(!categories.contains(Category::Cn));
+
+        assert!(BidiCategory::all().all(|bidi| bidis & (1 << bidi.to_byte()) != 0));
+        assert_eq!(decomp_kinds, (1 << DECOMP_KINDS.len()) - 1);
+        assert!(mirrored);
+        assert!(fields.iter().all(|&present| present));
+
+        // Every value from 0 to 9 is used, so a digit field that decoded to a constant would fail
+        // here.
+        assert_eq!(decimal_digits, 0x3ff);
+        assert_eq!(digits, 0x3ff);
+
+        let a = data.get(u32::from('A')).unwrap();
+        assert_eq!(a.decimal_digit_value(), None);
+        assert_eq!(a.digit_value(), None);
+        assert_eq!(a.numeric_value(), None);
+
+        // `Attached_Below_Left` (200) is no longer used by any character.
+        assert!(CombiningClass::named_classes()
+            .filter(|ccc| ccc.value() != 200)
+            .all(|ccc| combining_classes[usize::from(ccc.value())]));
+    }
+
+    #[test]
+    fn test_len() {
+        let data = UnicodeData::new().unwrap();
+
+        let num_assigned = (0..=0x10ffff)
+            .filter(|&codepoint| data.is_assigned(codepoint))
+            .count();
+
+        assert_eq!(data.len(), num_assigned);
+        assert!(!data.is_empty());
+    }
+
+    #[test]
+    fn test_bool_property() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.bool_property(0x20, "White_Space"), Some(true));
+        assert_eq!(data.bool_property(0x20, "WSpace"), Some(true));
+        assert_eq!(data.bool_property(0x41, "White_Space"), Some(false));
+        assert_eq!(data.bool_property(0x2010, "Dash"), Some(true));
+        assert_eq!(data.bool_property(0x2010, "Hyphen"), Some(true));
+        assert_eq!(data.bool_property(0x2014, "Hyphen"), Some(false));
+        assert_eq!(data.bool_property(0x2018, "QMark"), Some(true));
+        assert_eq!(data.bool_property(0x21, "Terminal_Punctuation"), Some(true));
+        assert_eq!(data.bool_property(0x4e00, "Unified_Ideograph"), Some(true));
+        assert_eq!(data.bool_property(0xf900, "Ideographic"), Some(true));
+        assert_eq!(data.bool_property(0x28, "Bidi_Mirrored"), Some(true));
+        assert_eq!(data.bool_property(0x378, "Dash"), Some(false));
+
+        assert_eq!(data.bool_property(0x20, "white_space"), None);
+        assert_eq!(data.bool_property(0xad, "Default_Ignorable_Code_Point"), None);
+        assert_eq!(data.bool_property(0x20, ""), None);
+    }
+
+    #[test]
+    fn test_lookup_name() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.lookup_name("LATIN CAPITAL LETTER A"), Some(0x41));
+        assert_eq!(data.lookup_name("latin capital letter a"), Some(0x41));
+        assert_eq!(data.lookup_name("NULL"), Some(0x0));
+        assert_eq!(data.lookup_name("LINE FEED"), Some(0xa));
+        assert_eq!(data.lookup_name("LINE FEED (LF)"), Some(0xa));
+        assert_eq!(data.lookup_name("LF"), Some(0xa));
+        assert_eq!(data.lookup_name("NEL"), Some(0x85));
+        assert_eq!(data.lookup_name("CHARACTER TABULATION"), Some(0x9));
+
+        // Current names take precedence over Unicode 1.0 names.
+        assert_eq!(data.lookup_name("BROKEN BAR"), Some(0xa6));
+        assert_eq!(data.lookup_name("BROKEN VERTICAL BAR"), Some(0xa6));
+
+        assert_eq!(data.lookup_name("<control>"), None);
+        assert_eq!(data.lookup_name("CJK Ideograph Extension A"), None);
+        assert_eq!(data.lookup_name("TAB"), None);
+        assert_eq!(data.lookup_name(""), None);
+    }
+
+    #[test]
+    fn test_lookup_unicode_1_name() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.lookup_unicode_1_name("NULL"), Some(0x0));
+        assert_eq!(data.lookup_unicode_1_name("LINE FEED (LF)"), Some(0xa));
+        assert_eq!(data.lookup_unicode_1_name("form feed (ff)"), Some(0xc));
+        assert_eq!(data.lookup_unicode_1_name("BROKEN VERTICAL BAR"), Some(0xa6));
+
+        // Current names are not Unicode 1.0 names.
+        assert_eq!(data.lookup_unicode_1_name("BROKEN BAR"), None);
+        assert_eq!(data.lookup_unicode_1_name(""), None);
+    }
+
+    #[test]
+    fn test_iter_where() {
+        let data = UnicodeData::new().unwrap();
+
+        let mut currency_symbols = data.iter_where(|c| c.category() == Category::Sc);
+        assert_eq!(currency_symbols.next().map(|(codepoint, _)| codepoint), Some(0x24));
+        assert_eq!(currency_symbols.count(), 62);
+
+        assert_eq!(data.iter_where(|c| c.category() == Category::Lt).count(), 31);
+
+        assert!(data
+            .iter_where(|c| c.category() == Category::Lt)
+            .all(|(codepoint, c)| c.codepoint() == codepoint));
+
+        assert!(data
+            .iter_where(|c| c.name() == "LATIN CAPITAL LETTER A")
+            .map(|(codepoint, _)| codepoint)
+            .eq([0x41]));
+
+        // Every codepoint in a shared range should be yielded.
+        assert_eq!(
+            data.iter_where(|c| c.name() == "CJK Ideograph Extension A").count(),
+            0x4dbf - 0x3400 + 1
+        );
+        assert!(data
+            .iter_where(|c| c.name() == "CJK Ideograph Extension A")
+            .map(|(codepoint, _)| codepoint)
+            .eq(0x3400..=0x4dbf));
+
+        assert_eq!(data.iter_where(|_| false).count(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unicode_subset() {
+        use super::UnicodeSubset;
+
+        let data = UnicodeData::new().unwrap();
+        let subset = UnicodeSubset::new(data, &['a', '\u{e9}', '\u{4e00}', '\u{378}', 'a']);
+
+        assert_eq!(subset.len(), 3);
+        assert!(!subset.is_empty());
+        assert_eq!(subset.get('a').unwrap().name(), "LATIN SMALL LETTER A");
+        assert_eq!(subset.get('\u{e9}').unwrap().codepoint(), 0xe9);
+        assert_eq!(subset.get('\u{4e00}').unwrap().codepoint(), 0x4e00);
+        assert!(subset.get('\u{378}').is_none());
+        assert!(subset.get('b').is_none());
+
+        assert!(UnicodeSubset::new(data, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_decomposition_depth() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.decomposition_depth('a'), 0);
+        assert_eq!(data.decomposition_depth('\u{301}'), 0);
+        assert_eq!(data.decomposition_depth('\u{e9}'), 1);
+        assert_eq!(data.decomposition_depth('\u{212b}'), 2);
+        assert_eq!(data.decomposition_depth('\u{1d5}'), 2);
+        assert_eq!(data.decomposition_depth('\u{1f82}'), 3);
+
+        // Compatibility decompositions are not followed.
+        assert_eq!(data.decomposition_depth('\u{fb00}'), 0);
+
+        assert_eq!(data.decomposition_depth('\u{ac00}'), 1);
+        assert_eq!(data.decomposition_depth('\u{d4db}'), 2);
+        assert_eq!(data.decomposition_depth('\u{1100}'), 0);
+    }
+
+    #[test]
+    fn test_name() {
+        let data = UnicodeData::new().unwrap();
+
+        assert_eq!(data.name(0x41), Some("LATIN CAPITAL LETTER A"));
+        assert_eq!(data.name(0x1f600), Some("GRINNING FACE"));
+        assert_eq!(data.name(0x4e01), Some("CJK Ideograph"));
+        assert_eq!(data.name(0x378), None);
+        assert_eq!(data.name(0x110000), None);
+
+        for codepoint in (0..0x30000).step_by(7) {
+            assert_eq!(data.name(codepoint), data.get(codepoint).map(|c| c.name()));
+        }
+    }
 }
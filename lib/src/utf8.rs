@@ -1,4 +1,4 @@
-use core::iter::Peekable;
+use core::{fmt, iter::{FilterMap, Peekable}};
 
 pub trait ToByte {
     fn to_byte(self) -> u8;
@@ -29,11 +29,50 @@ where
     }
 }
 
+/// Returns the number of bytes needed to encode the given character as UTF-8.
+#[must_use]
+pub fn char_len(c: char) -> usize {
+    match u32::from(c) {
+        0x0000..=0x007f => 1,
+        0x0080..=0x07ff => 2,
+        0x0800..=0xffff => 3,
+        _ => 4,
+    }
+}
+
+/// Returns the total length in bytes of the UTF-8 sequence starting with the given byte, or `None`
+/// if the byte cannot start a UTF-8 sequence.
+#[must_use]
+pub fn first_byte_len(byte: u8) -> Option<usize> {
+    match byte {
+        0x00..=0x7f => Some(1),
+        0xc2..=0xdf => Some(2),
+        0xe0..=0xef => Some(3),
+        0xf0..=0xf4 => Some(4),
+        _ => None,
+    }
+}
+
+/// Iterator returned by [`Utf8Decode::decode_utf8_skip`].
+pub type Utf8SkipDecoder<I, B> = FilterMap<
+    Utf8Decoder<I, B>,
+    fn(Result<char, Utf8Error>) -> Option<char>
+>;
+
 pub trait Utf8Decode {
     type Iter: Iterator<Item = Self::Byte>;
     type Byte: ToByte;
 
     fn decode_utf8(self) -> Utf8Decoder<Self::Iter, Self::Byte>;
+
+    /// Decodes UTF-8, silently dropping any invalid sequences rather than reporting them or
+    /// replacing them with U+FFFD REPLACEMENT CHARACTER.
+    fn decode_utf8_skip(self) -> Utf8SkipDecoder<Self::Iter, Self::Byte>
+    where
+        Self: Sized,
+    {
+        self.decode_utf8().filter_map(Result::ok)
+    }
 }
 
 impl<T, B> Utf8Decode for T
@@ -49,6 +88,94 @@ where
     }
 }
 
+pub trait Utf8DecodeRev {
+    type Iter: DoubleEndedIterator<Item = Self::Byte>;
+    type Byte: ToByte;
+
+    fn decode_utf8_rev(self) -> Utf8RevDecoder<Self::Iter, Self::Byte>;
+}
+
+impl<T, B> Utf8DecodeRev for T
+where
+    T: IntoIterator<Item = B>,
+    <T as IntoIterator>::IntoIter: DoubleEndedIterator,
+    B: ToByte,
+{
+    type Iter = <T as IntoIterator>::IntoIter;
+    type Byte = B;
+
+    fn decode_utf8_rev(self) -> Utf8RevDecoder<Self::Iter, B> {
+        Utf8RevDecoder::new(self.into_iter())
+    }
+}
+
+pub trait Utf8Encode {
+    type Iter: Iterator<Item = char>;
+
+    fn encode_utf8(self) -> Utf8Encoder<Self::Iter>;
+}
+
+impl<T> Utf8Encode for T
+where
+    T: IntoIterator<Item = char>,
+{
+    type Iter = <T as IntoIterator>::IntoIter;
+
+    fn encode_utf8(self) -> Utf8Encoder<Self::Iter> {
+        Utf8Encoder::new(self.into_iter())
+    }
+}
+
+/// Encodes characters as UTF-8, yielding the encoded bytes.
+pub struct Utf8Encoder<I> {
+    chars: I,
+    buf: [u8; 4],
+    len: u8,
+    index: u8,
+}
+
+impl<I> Utf8Encoder<I>
+where
+    I: Iterator<Item = char>,
+{
+    fn new(chars: I) -> Self {
+        Self {
+            chars,
+            buf: [0; 4],
+            len: 0,
+            index: 0,
+        }
+    }
+}
+
+impl<I> Iterator for Utf8Encoder<I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            let c = self.chars.next()?;
+            self.len = c.encode_utf8(&mut self.buf).len() as u8;
+            self.index = 0;
+        }
+
+        let byte = self.buf[usize::from(self.index)];
+        self.index += 1;
+        Some(byte)
+    }
+}
+
+/// Encodes the given characters as UTF-8.
+#[cfg(feature = "std")]
+pub fn encode_to_vec<I>(chars: I) -> Vec<u8>
+where
+    I: IntoIterator<Item = char>,
+{
+    chars.encode_utf8().collect()
+}
+
 // https://encoding.spec.whatwg.org/#utf-8-decoder
 pub struct Utf8Decoder<I, B>
 where
@@ -56,6 +183,9 @@ where
     B: ToByte,
 {
     bytes: Peekable<I>,
+    // The next decoded item, if it has already been decoded by `peek_char`.
+    peeked: Option<Option<Result<char, Utf8Error>>>,
+    max_scalar: u32,
 }
 
 impl<I, B> Utf8Decoder<I, B>
@@ -66,8 +196,50 @@ where
     fn new(bytes: I) -> Self {
         Self {
             bytes: bytes.peekable(),
+            peeked: None,
+            max_scalar: u32::from(char::MAX),
+        }
+    }
+
+    /// Rejects characters above the given scalar value, so that decoding one yields a
+    /// [`Utf8ErrorKind::AboveMaxScalar`] error containing the bytes of the whole sequence. For
+    /// example, a maximum of 0xffff restricts the decoded characters to the BMP, as needed when the
+    /// text will be passed on as UCS-2. This is stricter than UTF-8 requires, so by default all
+    /// scalar values are accepted.
+    #[must_use]
+    pub fn max_scalar(mut self, max: u32) -> Self {
+        self.max_scalar = max;
+        self
+    }
+
+    /// Returns a reference to the next decoded character or error without consuming it, or `None`
+    /// if there are no more bytes to decode.
+    pub fn peek_char(&mut self) -> Option<&Result<char, Utf8Error>> {
+        let peeked = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.next(),
+        };
+
+        self.peeked.insert(peeked).as_ref()
+    }
+
+    /// Returns an iterator which yields each decoded character or error along with its line and
+    /// column; see [`LineCol`] for how they are counted.
+    pub fn with_position(self) -> Utf8PositionDecoder<I, B> {
+        Utf8PositionDecoder {
+            decoder: self,
+            pos: LineCol { line: 1, col: 1 },
         }
     }
+
+    /// Consumes the decoder, returning the number of characters successfully decoded and the
+    /// number of invalid sequences encountered.
+    pub fn count_errors(self) -> (usize, usize) {
+        self.fold((0, 0), |(chars, errors), res| match res {
+            Ok(_) => (chars + 1, errors),
+            Err(_) => (chars, errors + 1),
+        })
+    }
 }
 
 impl<I, B> Iterator for Utf8Decoder<I, B>
@@ -79,6 +251,10 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         const DEFAULT_BOUNDARIES: (u8, u8) = (0x80, 0xbf);
+
+        if let Some(peeked) = self.peeked.take() {
+            return peeked;
+        }
         
         // Keep track of the bytes we have seen so far, so that if there is an error we can return
         // the problematic bytes. There is no need for a variable to store the number of bytes we
@@ -94,10 +270,19 @@ where
         bytes_seen[0] = first_byte;
 
         match first_byte {
-            byte @ 0x00..=0x7f => {
+            byte @ 0x00..=0x7f if u32::from(byte) <= self.max_scalar => {
                 return Some(Ok(char::from(byte)));
             },
 
+            0x00..=0x7f => {
+                return Some(Err(Utf8Error {
+                    bad_bytes: bytes_seen,
+                    num_bad_bytes: 1,
+                    num_consumed_bad_bytes: 1,
+                    kind: Utf8ErrorKind::AboveMaxScalar,
+                }));
+            },
+
             byte @ 0xc2..=0xdf => {
                 bytes_needed = 1;
                 codepoint = u32::from(byte & 0x1f) << 6;
@@ -124,11 +309,12 @@ where
                 };
             },
 
-            _ => {
+            byte => {
                 return Some(Err(Utf8Error {
                     bad_bytes: bytes_seen,
                     num_bad_bytes: 1,
                     num_consumed_bad_bytes: 1,
+                    kind: Utf8ErrorKind::for_invalid_first_byte(byte),
                 }));
             },
         }
@@ -141,7 +327,8 @@ where
                 None => return Some(Err(Utf8Error {
                     bad_bytes: bytes_seen,
                     num_bad_bytes: usize::from(i) + 1,
-                    num_consumed_bad_bytes: usize::from(i),
+                    num_consumed_bad_bytes: usize::from(i) + 1,
+                    kind: Utf8ErrorKind::Truncated,
                 })),
             };
 
@@ -152,6 +339,7 @@ where
                     bad_bytes: bytes_seen,
                     num_bad_bytes: usize::from(i) + 2,
                     num_consumed_bad_bytes: usize::from(i) + 1,
+                    kind: Utf8ErrorKind::for_out_of_bounds_byte(first_byte, byte),
                 }));
             }
 
@@ -164,6 +352,16 @@ where
             codepoint |= u32::from(byte & 0x3f) << (6 * (bytes_needed - i - 1));
         }
 
+        if codepoint > self.max_scalar {
+            let num_bytes = usize::from(bytes_needed) + 1;
+            return Some(Err(Utf8Error {
+                bad_bytes: bytes_seen,
+                num_bad_bytes: num_bytes,
+                num_consumed_bad_bytes: num_bytes,
+                kind: Utf8ErrorKind::AboveMaxScalar,
+            }));
+        }
+
         let codepoint = char::try_from(codepoint)
             .unwrap();
 
@@ -171,10 +369,251 @@ where
     }
 }
 
+/// Iterator returned by [`Utf8Decoder::with_position`].
+pub struct Utf8PositionDecoder<I, B>
+where
+    I: Iterator<Item = B>,
+    B: ToByte,
+{
+    decoder: Utf8Decoder<I, B>,
+    pos: LineCol,
+}
+
+impl<I, B> Iterator for Utf8PositionDecoder<I, B>
+where
+    I: Iterator<Item = B>,
+    B: ToByte,
+{
+    type Item = (LineCol, Result<char, Utf8Error>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let res = self.decoder.next()?;
+        let pos = self.pos;
+
+        if let Ok('\n') = res {
+            self.pos = LineCol { line: pos.line + 1, col: 1 };
+        } else {
+            self.pos.col += 1;
+        }
+
+        Some((pos, res))
+    }
+}
+
+/// The 1-based line and column of a decoded character or error.
+///
+/// Only U+000A LINE FEED starts a new line, so a CRLF line ending counts as a single line break
+/// with the carriage return at the end of the line, and a lone carriage return does not start a
+/// new line. The line feed itself is at the end of the line it ends. Each character or invalid
+/// sequence advances the column by one, including combining marks and other characters which do
+/// not take up a column of their own when displayed.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl fmt::Display for LineCol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+/// Decodes UTF-8 from a byte slice in the same way as [`Utf8Decoder`]. Because the source is a
+/// slice, the decoder can also report exactly which bytes each character or error was decoded from;
+/// see [`Utf8SliceDecoder::with_source`].
+#[derive(Clone, Debug)]
+pub struct Utf8SliceDecoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Utf8SliceDecoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the bytes which have not been decoded yet.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns an iterator which yields each decoded character or error along with the sub-slice
+    /// of the source bytes it was decoded from. The sub-slices are contiguous and together cover
+    /// the entire source.
+    pub fn with_source(self) -> Utf8SourceDecoder<'a> {
+        Utf8SourceDecoder { decoder: self }
+    }
+
+    fn next_with_source(&mut self) -> Option<(Result<char, Utf8Error>, &'a [u8])> {
+        let res = self.bytes.decode_utf8().next()?;
+
+        let len = match &res {
+            Ok(c) => char_len(*c),
+            Err(err) => err.num_consumed_bad_bytes,
+        };
+
+        let (source, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Some((res, source))
+    }
+}
+
+impl<'a> Iterator for Utf8SliceDecoder<'a> {
+    type Item = Result<char, Utf8Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_source().map(|(res, _)| res)
+    }
+}
+
+/// Iterator returned by [`Utf8SliceDecoder::with_source`].
+#[derive(Clone, Debug)]
+pub struct Utf8SourceDecoder<'a> {
+    decoder: Utf8SliceDecoder<'a>,
+}
+
+impl<'a> Iterator for Utf8SourceDecoder<'a> {
+    type Item = (Result<char, Utf8Error>, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.next_with_source()
+    }
+}
+
+/// Decodes UTF-8 starting from the end of the byte source, yielding characters in reverse order.
+///
+/// When the bytes at the end of the source do not form a valid UTF-8 sequence, the final byte is
+/// reported as an error on its own and decoding resumes from the byte before it. The same bytes
+/// are therefore rejected as by [`Utf8Decoder`], but the way they are grouped into errors may
+/// differ.
+pub struct Utf8RevDecoder<I, B>
+where
+    I: DoubleEndedIterator<Item = B>,
+    B: ToByte,
+{
+    bytes: I,
+    // Bytes which have been taken from the back of `bytes` but not yet decoded, stored in
+    // forwards order; `pending[num_pending - 1]` is the next byte to be decoded.
+    pending: [u8; 3],
+    num_pending: usize,
+}
+
+impl<I, B> Utf8RevDecoder<I, B>
+where
+    I: DoubleEndedIterator<Item = B>,
+    B: ToByte,
+{
+    fn new(bytes: I) -> Self {
+        Self {
+            bytes,
+            pending: [0u8; 3],
+            num_pending: 0,
+        }
+    }
+
+    fn next_byte_back(&mut self) -> Option<u8> {
+        if self.num_pending > 0 {
+            self.num_pending -= 1;
+            Some(self.pending[self.num_pending])
+        } else {
+            self.bytes.next_back().map(ToByte::to_byte)
+        }
+    }
+
+    fn push_pending(&mut self, byte: u8) {
+        self.pending[self.num_pending] = byte;
+        self.num_pending += 1;
+    }
+}
+
+impl<I, B> Iterator for Utf8RevDecoder<I, B>
+where
+    I: DoubleEndedIterator<Item = B>,
+    B: ToByte,
+{
+    type Item = Result<char, Utf8Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last_byte = self.next_byte_back()?;
+
+        let single_byte_error = |kind| Utf8Error {
+            bad_bytes: [last_byte, 0, 0, 0],
+            num_bad_bytes: 1,
+            num_consumed_bad_bytes: 1,
+            kind,
+        };
+
+        match last_byte {
+            0x00..=0x7f => return Some(Ok(char::from(last_byte))),
+            0x80..=0xbf => (),
+            0xc2..=0xf4 => return Some(Err(single_byte_error(Utf8ErrorKind::Truncated))),
+            _ => {
+                let kind = Utf8ErrorKind::for_invalid_first_byte(last_byte);
+                return Some(Err(single_byte_error(kind)));
+            },
+        }
+
+        // Walk backwards over up to three more bytes to find the byte which should be the start of
+        // the sequence. The sequence is stored at the end of `buf`, in forwards order.
+        let mut buf = [0u8; 4];
+        buf[3] = last_byte;
+        let mut len = 1;
+
+        while len < buf.len() {
+            let Some(byte) = self.next_byte_back() else {
+                break;
+            };
+
+            buf[buf.len() - len - 1] = byte;
+            len += 1;
+
+            if !(0x80..=0xbf).contains(&byte) {
+                break;
+            }
+        }
+
+        let seq = &buf[(buf.len() - len)..];
+
+        // Use the forwards decoder to check that the sequence we found is exactly one valid
+        // character.
+        let mut decoder = seq.decode_utf8();
+        if let (Some(Ok(c)), None) = (decoder.next(), decoder.next()) {
+            return Some(Ok(c));
+        }
+
+        // The sequence was invalid, so report only the final byte as an error and put the rest of
+        // the sequence back so that it can be decoded again.
+        for &byte in &seq[..(len - 1)] {
+            self.push_pending(byte);
+        }
+
+        Some(Err(single_byte_error(Utf8ErrorKind::UnexpectedContinuation)))
+    }
+}
+
+/// Checks whether the given bytes are valid UTF-8. If they are not, returns the byte offset of the
+/// start of the first invalid sequence, along with the reason it is invalid.
+pub fn validate(bytes: &[u8]) -> Result<(), (usize, Utf8ErrorKind)> {
+    let mut offset = 0;
+
+    for res in bytes.decode_utf8() {
+        match res {
+            Ok(c) => offset += char_len(c),
+            Err(err) => return Err((offset, err.kind())),
+        }
+    }
+
+    Ok(())
+}
+
 pub struct Utf8Error {
     bad_bytes: [u8; 4],
     num_bad_bytes: usize,
     num_consumed_bad_bytes: usize,
+    kind: Utf8ErrorKind,
 }
 
 impl Utf8Error {
@@ -182,15 +621,113 @@ impl Utf8Error {
         &self.bad_bytes[..self.num_bad_bytes]
     }
 
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> Utf8ErrorKind {
+        self.kind
+    }
+
     // FIXME: return some type with u8 array + length
     pub fn into_parts(self) -> ([u8; 4], usize, usize) {
         (self.bad_bytes, self.num_bad_bytes, self.num_consumed_bad_bytes)
     }
 }
 
+impl fmt::Debug for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Utf8Error")
+            .field("bytes", &self.bytes())
+            .field("num_consumed_bytes", &self.num_consumed_bad_bytes)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid UTF-8 sequence:")?;
+        for byte in self.bytes() {
+            write!(f, " 0x{:02x}", byte)?;
+        }
+        write!(f, " ({})", self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Utf8Error {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Utf8ErrorKind {
+    /// A continuation byte (0x80 to 0xbf) appeared where the start of a sequence was expected.
+    UnexpectedContinuation,
+    /// A byte which never appears in UTF-8 (0xf8 to 0xff) was found.
+    InvalidByte,
+    /// A byte other than a continuation byte appeared where a continuation byte was expected.
+    InvalidContinuation,
+    /// The sequence encodes a codepoint using more bytes than necessary.
+    Overlong,
+    /// The sequence encodes a surrogate codepoint (U+D800 to U+DFFF).
+    Surrogate,
+    /// The sequence encodes a codepoint greater than U+10FFFF.
+    TooLarge,
+    /// The input ended before the end of the sequence.
+    Truncated,
+    /// The sequence is valid, but encodes a scalar value above the maximum set with
+    /// [`Utf8Decoder::max_scalar`].
+    AboveMaxScalar,
+}
+
+impl fmt::Display for Utf8ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedContinuation => write!(f, "unexpected continuation byte"),
+            Self::InvalidByte => write!(f, "invalid byte"),
+            Self::InvalidContinuation => write!(f, "expected continuation byte"),
+            Self::Overlong => write!(f, "overlong encoding"),
+            Self::Surrogate => write!(f, "encoded surrogate"),
+            Self::TooLarge => write!(f, "codepoint above U+10FFFF"),
+            Self::Truncated => write!(f, "truncated sequence"),
+            Self::AboveMaxScalar => write!(f, "scalar value above maximum"),
+        }
+    }
+}
+
+impl Utf8ErrorKind {
+    fn for_invalid_first_byte(byte: u8) -> Self {
+        match byte {
+            0x80..=0xbf => Self::UnexpectedContinuation,
+            0xc0 | 0xc1 => Self::Overlong,
+            0xf5..=0xf7 => Self::TooLarge,
+            _ => Self::InvalidByte,
+        }
+    }
+
+    fn for_out_of_bounds_byte(first_byte: u8, byte: u8) -> Self {
+        // Only the second byte of a sequence can be a continuation byte and still be out of
+        // bounds, as all later bytes use the default bounds of 0x80 to 0xbf.
+        match (first_byte, byte) {
+            (_, 0x00..=0x7f | 0xc0..=0xff) => Self::InvalidContinuation,
+            (0xe0, _) | (0xf0, _) => Self::Overlong,
+            (0xed, _) => Self::Surrogate,
+            (0xf4, _) => Self::TooLarge,
+            _ => Self::InvalidContinuation,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Utf8Decode;
+    use super::{
+        char_len,
+        first_byte_len,
+        validate,
+        LineCol,
+        Utf8Decode,
+        Utf8DecodeRev,
+        Utf8Encode,
+        Utf8ErrorKind,
+        Utf8SliceDecoder,
+    };
 
     #[derive(PartialEq, Eq, Debug)]
     struct Invalid;
@@ -249,6 +786,307 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_utf8_decoder_truncated_at_end() {
+        // Every byte of a sequence cut short by the end of the input has been consumed, since
+        // there is no following byte left to be decoded again.
+        let err = [0xe1, 0xbd].decode_utf8().next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), Utf8ErrorKind::Truncated);
+        assert_eq!(err.bytes(), &[0xe1, 0xbd]);
+        assert_eq!(err.into_parts().2, 2);
+
+        let err = [0x61, 0xf0].decode_utf8().nth(1).unwrap().unwrap_err();
+        assert_eq!(err.kind(), Utf8ErrorKind::Truncated);
+        assert_eq!(err.into_parts().2, 1);
+    }
+
+    #[test]
+    fn test_utf8_rev_decoder() {
+        assert_rev_decodes_to(&[
+            0x68, 0x65, 0x6c, 0x6c, 0x6f
+        ], &[
+            Ok('o'), Ok('l'), Ok('l'), Ok('e'), Ok('h')
+        ]);
+
+        assert_rev_decodes_to(&[
+            0xce, 0xba, 0xe1, 0xbd, 0xb9, 0xcf, 0x83, 0xce, 0xbc, 0xce, 0xb5
+        ], &[
+            Ok('ε'), Ok('μ'), Ok('σ'), Ok('ό'), Ok('κ')
+        ]);
+
+        assert_rev_decodes_to(&[
+            0xf0, 0x9f, 0x8f, 0xb3, 0xef, 0xb8, 0x8f, 0xe2, 0x80, 0x8d, 0xe2, 0x9a, 0xa7, 0xef,
+            0xb8, 0x8f
+        ], &[
+            Ok('\u{fe0f}'), Ok('\u{26a7}'), Ok('\u{200d}'), Ok('\u{fe0f}'), Ok('\u{1f3f3}')
+        ]);
+
+        assert_rev_decodes_to(&[
+            0xce, 0x61
+        ], &[
+            Ok('a'), INVALID
+        ]);
+
+        assert_rev_decodes_to(&[
+            0x61, 0x80
+        ], &[
+            INVALID, Ok('a')
+        ]);
+
+        assert_rev_decodes_to(&[
+            0x80, 0x80, 0x80, 0x80, 0x80
+        ], &[
+            INVALID, INVALID, INVALID, INVALID, INVALID
+        ]);
+
+        assert_rev_decodes_to(&[
+            0xed, 0x86, 0xad, 0xed, 0xba, 0xad
+        ], &[
+            INVALID, INVALID, INVALID, Ok('\u{d1ad}')
+        ]);
+
+        assert_rev_decodes_to(&[
+            0x61, 0xe2, 0x82
+        ], &[
+            INVALID, INVALID, Ok('a')
+        ]);
+    }
+
+    #[test]
+    fn test_char_len() {
+        let boundaries = [
+            '\u{0}', '\u{7f}', '\u{80}', '\u{7ff}', '\u{800}', '\u{ffff}', '\u{10000}', '\u{10ffff}'
+        ];
+
+        for c in boundaries {
+            assert_eq!(char_len(c), c.len_utf8());
+        }
+
+        assert_eq!(char_len('\u{7ff}'), 2);
+        assert_eq!(char_len('\u{800}'), 3);
+    }
+
+    #[test]
+    fn test_first_byte_len() {
+        for c in ['a', '\u{80}', '\u{7ff}', '\u{800}', '\u{ffff}', '\u{10000}', '\u{10ffff}'] {
+            let mut buf = [0u8; 4];
+            let encoded = c.encode_utf8(&mut buf);
+            assert_eq!(first_byte_len(encoded.as_bytes()[0]), Some(encoded.len()));
+        }
+
+        for byte in [0x80, 0xbf, 0xc0, 0xc1, 0xf5, 0xff] {
+            assert_eq!(first_byte_len(byte), None);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate(b""), Ok(()));
+        assert_eq!(validate("h\u{e9}llo \u{1f600}".as_bytes()), Ok(()));
+        assert_eq!(validate(&[0x61, 0x80]), Err((1, Utf8ErrorKind::UnexpectedContinuation)));
+        assert_eq!(validate(&[0x61, 0xff]), Err((1, Utf8ErrorKind::InvalidByte)));
+        assert_eq!(validate(&[0xce, 0x61]), Err((0, Utf8ErrorKind::InvalidContinuation)));
+        assert_eq!(validate(&[0xc0, 0xaf]), Err((0, Utf8ErrorKind::Overlong)));
+        assert_eq!(validate(&[0xe0, 0x80, 0xaf]), Err((0, Utf8ErrorKind::Overlong)));
+        assert_eq!(validate(&[0xf0, 0x80, 0x80, 0xaf]), Err((0, Utf8ErrorKind::Overlong)));
+        assert_eq!(validate(&[0x61, 0xed, 0xa0, 0x80]), Err((1, Utf8ErrorKind::Surrogate)));
+        assert_eq!(validate(&[0xf4, 0x90, 0x80, 0x80]), Err((0, Utf8ErrorKind::TooLarge)));
+        assert_eq!(validate(&[0xf5, 0x80, 0x80, 0x80]), Err((0, Utf8ErrorKind::TooLarge)));
+        assert_eq!(validate(&[0xce, 0xba, 0xe1, 0xbd]), Err((2, Utf8ErrorKind::Truncated)));
+    }
+
+    #[test]
+    fn test_count_errors() {
+        assert_eq!([0u8; 0].decode_utf8().count_errors(), (0, 0));
+        assert_eq!(b"hello".decode_utf8().count_errors(), (5, 0));
+        assert_eq!([0xce, 0x61].decode_utf8().count_errors(), (1, 1));
+        assert_eq!([0xed, 0x86, 0xad, 0xed, 0xba, 0xad].decode_utf8().count_errors(), (1, 3));
+    }
+
+    #[test]
+    fn test_utf8_encoder() {
+        assert!(['\0'; 0].encode_utf8().eq([]));
+        assert!("hello".chars().encode_utf8().eq(*b"hello"));
+        assert!("κόσμε".chars().encode_utf8().eq("κόσμε".bytes()));
+        assert!(['\u{7f}', '\u{80}', '\u{7ff}', '\u{800}', '\u{ffff}', '\u{10000}', '\u{10ffff}']
+            .encode_utf8()
+            .eq([
+                0x7f, 0xc2, 0x80, 0xdf, 0xbf, 0xe0, 0xa0, 0x80, 0xef, 0xbf, 0xbf, 0xf0, 0x90,
+                0x80, 0x80, 0xf4, 0x8f, 0xbf, 0xbf,
+            ]));
+
+        // Encoding the decoder's output should give back the original valid input.
+        let bytes = [
+            0xf0, 0x9f, 0x8f, 0xb3, 0xef, 0xb8, 0x8f, 0xe2, 0x80, 0x8d, 0xe2, 0x9a, 0xa7, 0xef,
+            0xb8, 0x8f
+        ];
+        assert!(bytes.decode_utf8_skip().encode_utf8().eq(bytes));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_encode_to_vec() {
+        use super::encode_to_vec;
+
+        let s = "h\u{e9}llo \u{1f600}";
+        assert_eq!(encode_to_vec(s.chars()), s.as_bytes());
+        assert!(encode_to_vec([]).is_empty());
+    }
+
+    #[test]
+    fn test_peek_char() {
+        let mut decoder = [0x68, 0xce, 0xba, 0xff].decode_utf8();
+
+        assert_eq!(decoder.peek_char().map(|res| res.as_ref().ok().copied()), Some(Some('h')));
+        assert_eq!(decoder.peek_char().map(|res| res.as_ref().ok().copied()), Some(Some('h')));
+        assert_eq!(decoder.next().map(|res| res.ok()), Some(Some('h')));
+
+        assert_eq!(decoder.peek_char().map(|res| res.as_ref().ok().copied()), Some(Some('κ')));
+        assert_eq!(decoder.next().map(|res| res.ok()), Some(Some('κ')));
+
+        assert!(decoder.peek_char().unwrap().is_err());
+        assert_eq!(decoder.peek_char().unwrap().as_ref().unwrap_err().bytes(), &[0xff]);
+        assert!(decoder.next().unwrap().is_err());
+
+        assert!(decoder.peek_char().is_none());
+        assert!(decoder.next().is_none());
+        assert!(decoder.peek_char().is_none());
+
+        let mut decoder = b"ab".decode_utf8();
+        decoder.peek_char();
+        assert_eq!(decoder.count_errors(), (2, 0));
+    }
+
+    #[test]
+    fn test_max_scalar() {
+        // "a😀b"
+        let bytes = [0x61, 0xf0, 0x9f, 0x98, 0x80, 0x62];
+
+        let mut decoder = bytes.decode_utf8().max_scalar(0xffff);
+        assert_eq!(decoder.next().map(|res| res.ok()), Some(Some('a')));
+        let err = decoder.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), Utf8ErrorKind::AboveMaxScalar);
+        assert_eq!(err.bytes(), &[0xf0, 0x9f, 0x98, 0x80]);
+        assert_eq!(decoder.next().map(|res| res.ok()), Some(Some('b')));
+        assert!(decoder.next().is_none());
+
+        assert_eq!(bytes.decode_utf8().max_scalar(0x1f600).count_errors(), (3, 0));
+        assert_eq!(bytes.decode_utf8().max_scalar(0x61).count_errors(), (1, 2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_utf8_error_display() {
+        let err = [0xce, 0x61].decode_utf8().next().unwrap().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid UTF-8 sequence: 0xce 0x61 (expected continuation byte)"
+        );
+
+        let err = [0xed, 0xa0, 0x80].decode_utf8().next().unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "invalid UTF-8 sequence: 0xed 0xa0 (encoded surrogate)");
+
+        let err = [0xff].decode_utf8().next().unwrap().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err),
+            "Utf8Error { bytes: [255], num_consumed_bytes: 1, kind: InvalidByte }"
+        );
+    }
+
+    #[test]
+    fn test_with_position() {
+        let bytes = b"ab\r\nc\xff\ne\xcc\x81\n";
+
+        let positions = bytes
+            .decode_utf8()
+            .with_position()
+            .map(|(pos, res)| (pos.line, pos.col, res.ok()));
+
+        assert!(positions.eq([
+            (1, 1, Some('a')),
+            (1, 2, Some('b')),
+            (1, 3, Some('\r')),
+            (1, 4, Some('\n')),
+            (2, 1, Some('c')),
+            (2, 2, None),
+            (2, 3, Some('\n')),
+            (3, 1, Some('e')),
+            (3, 2, Some('\u{301}')),
+            (3, 3, Some('\n')),
+        ]));
+
+        assert_eq!(
+            bytes.decode_utf8().with_position().last().map(|(pos, _)| pos),
+            Some(LineCol { line: 3, col: 3 })
+        );
+        assert!(b"".decode_utf8().with_position().next().is_none());
+
+        #[cfg(feature = "std")]
+        assert_eq!(LineCol { line: 12, col: 5 }.to_string(), "12:5");
+    }
+
+    #[test]
+    fn test_decode_utf8_skip() {
+        assert!([0u8; 0].decode_utf8_skip().eq([]));
+        assert!(b"hello".decode_utf8_skip().eq("hello".chars()));
+
+        let bytes = [0xff, 0x68, 0xce, 0xba, 0xce, 0x61, 0xed, 0xa0, 0x80, 0x21, 0xe1, 0xbd];
+        assert!(bytes.decode_utf8_skip().eq(['h', 'κ', 'a', '!']));
+    }
+
+    #[test]
+    fn test_slice_decoder_with_source() {
+        let bytes = [0x68, 0xce, 0xba, 0xce, 0x61, 0xed, 0xa0, 0x80, 0xe1, 0xbd];
+
+        let expected: [(Result<char, Invalid>, &[u8]); 8] = [
+            (Ok('h'), &[0x68]),
+            (Ok('κ'), &[0xce, 0xba]),
+            (INVALID, &[0xce]),
+            (Ok('a'), &[0x61]),
+            (INVALID, &[0xed]),
+            (INVALID, &[0xa0]),
+            (INVALID, &[0x80]),
+            (INVALID, &[0xe1, 0xbd]),
+        ];
+
+        let mut decoded = Utf8SliceDecoder::new(&bytes).with_source();
+
+        for (expected_char, expected_source) in expected {
+            let (decoded_char, source) = decoded.next().unwrap();
+            assert_eq!(decoded_char.map_err(|_| Invalid), expected_char);
+            assert_eq!(source, expected_source);
+        }
+
+        assert!(decoded.next().is_none());
+
+        // Each source slice should point into the original buffer.
+        let mut offset = 0;
+        for (_, source) in Utf8SliceDecoder::new(&bytes).with_source() {
+            assert_eq!(source.as_ptr(), bytes[offset..].as_ptr());
+            offset += source.len();
+        }
+        assert_eq!(offset, bytes.len());
+
+        assert!(Utf8SliceDecoder::new(&bytes)
+            .map(|res| res.map_err(|_| Invalid))
+            .eq(bytes.decode_utf8().map(|res| res.map_err(|_| Invalid))));
+
+        let mut decoder = Utf8SliceDecoder::new(b"ab");
+        decoder.next();
+        assert_eq!(decoder.remaining(), b"b");
+    }
+
+    fn assert_rev_decodes_to(bytes: &[u8], expected: &[Result<char, Invalid>]) {
+        let mut decoded = bytes.decode_utf8_rev();
+
+        for expected_char in expected {
+            let decoded_char = decoded.next().map(|res| res.map_err(|_| Invalid));
+            assert_eq!(decoded_char.as_ref(), Some(expected_char));
+        }
+
+        assert!(decoded.next().is_none());
+    }
+
     fn assert_decodes_to(bytes: &[u8], expected: &[Result<char, Invalid>]) {
         let mut decoded = bytes.decode_utf8();
 
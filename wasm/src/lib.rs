@@ -15,9 +15,8 @@ impl WbgCharData {
 
     #[wasm_bindgen]
     pub fn encoded_utf8(&self) -> Option<EncodedCodepoint> {
-        let c = char::try_from(self.0.codepoint()).ok()?;
         let mut buf = [0u8; 4];
-        let len = c.encode_utf8(&mut buf).len() as u8;
+        let len = self.0.encode_utf8(&mut buf)?.len() as u8;
         Some(EncodedCodepoint::new(buf, len))
     }
 
@@ -33,9 +32,8 @@ impl WbgCharData {
     }
 
     fn encoded_utf16(&self) -> Option<([u16; 2], u8)> {
-        let c = char::try_from(self.0.codepoint()).ok()?;
         let mut word_buf = [0u16; 2];
-        let num_words = c.encode_utf16(&mut word_buf).len() as u8;
+        let num_words = self.0.encode_utf16(&mut word_buf)?.len() as u8;
         Some((word_buf, num_words))
     }
 
@@ -51,7 +49,7 @@ impl WbgCharData {
 
     #[wasm_bindgen]
     pub fn combining_class(&self) -> u8 {
-        self.0.combining_class().0
+        self.0.combining_class_value()
     }
 
     #[wasm_bindgen]